@@ -0,0 +1,322 @@
+//! Compile-time companion to `slang-reflector`.
+//!
+//! [`include_shader!`] compiles a Slang file while the Rust crate is being compiled and expands
+//! to a module describing its interface, so that Rust code referring to a binding or an entry
+//! point that doesn't exist fails to build.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use proc_macro2::{Span, TokenStream};
+use quote::{format_ident, quote};
+use syn::parse::{Parse, ParseStream};
+use syn::{Ident, LitBool, LitByteStr, LitStr, Token, parse_macro_input};
+
+use slang_reflector::{BoundParameter, CompileTarget, Reflector, RustCodegen};
+
+/// Compiles a Slang file and expands to a module named after it.
+///
+/// ```
+/// slang_reflector_macros::include_shader!(
+///     "../tests/str_attr.slang",
+///     target = "wgsl",
+///     profile = "spirv_1_6"
+/// );
+///
+/// let code = str_attr::code::FILL_BUFFER3;
+/// assert!(!code.is_empty());
+/// assert_eq!(str_attr::entry_points::FILL_BUFFER3, "fillBuffer3");
+/// assert_eq!(
+///     str_attr::bindings::OUTPUT_BUFFER,
+///     str_attr::bindings::Binding { index: 0, space: 0 }
+/// );
+/// let data = str_attr::AnnotatedUniform { prop1: 0 };
+/// ```
+///
+/// The path is relative to the crate's `Cargo.toml`, and the file's directory is searched for
+/// imported modules. Optional arguments:
+///
+/// - `target = "spirv"`: one of `spirv`, `wgsl`, `hlsl`, `glsl`, `metal` or `dxil`, SPIR-V by
+///   default,
+/// - `profile = "spirv_1_5"`: the target profile,
+/// - `bytemuck = true`: derive `bytemuck::Pod` and `bytemuck::Zeroable` on the structs, which
+///   needs `bytemuck` with the `derive` and `min_const_generics` features.
+///
+/// The module holds the `#[repr(C)]` structs generated by `RustCodegen`, plus:
+///
+/// - `bindings`: a `Binding` with index and space for every global resource,
+/// - `uniforms`: the byte offset of every global uniform,
+/// - `specialization_constants`: the constant ID of every specialization constant,
+/// - `entry_points`: the name of every entry point,
+/// - `code`: the compiled code of every entry point.
+///
+/// Constants are named after the shader variable in `SCREAMING_SNAKE_CASE`. The crate is
+/// rebuilt when the shader or any module it imports changes.
+///
+/// Two shader symbols that map to the same constant in one module, e.g. `outputBuffer` and
+/// `output_buffer`, are a compile error naming both:
+///
+/// ```compile_fail
+/// slang_reflector_macros::include_shader!("../tests/constant_name_collision.slang");
+/// ```
+#[proc_macro]
+pub fn include_shader(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as ShaderInput);
+    match expand(&input) {
+        Ok(tokens) => tokens.into(),
+        Err(error) => error.to_compile_error().into(),
+    }
+}
+
+struct ShaderInput {
+    path: LitStr,
+    target: CompileTarget,
+    profile: Option<String>,
+    bytemuck: bool,
+}
+
+impl Parse for ShaderInput {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let mut shader = ShaderInput {
+            path: input.parse()?,
+            target: CompileTarget::Spirv,
+            profile: None,
+            bytemuck: false,
+        };
+        while !input.is_empty() {
+            input.parse::<Token![,]>()?;
+            if input.is_empty() {
+                break;
+            }
+            let key: Ident = input.parse()?;
+            input.parse::<Token![=]>()?;
+            match key.to_string().as_str() {
+                "target" => {
+                    let target: LitStr = input.parse()?;
+                    shader.target = parse_target(&target)?;
+                }
+                "profile" => shader.profile = Some(input.parse::<LitStr>()?.value()),
+                "bytemuck" => shader.bytemuck = input.parse::<LitBool>()?.value,
+                _ => {
+                    return Err(syn::Error::new(
+                        key.span(),
+                        "expected `target`, `profile` or `bytemuck`",
+                    ));
+                }
+            }
+        }
+        Ok(shader)
+    }
+}
+
+fn parse_target(target: &LitStr) -> syn::Result<CompileTarget> {
+    Ok(match target.value().as_str() {
+        "spirv" => CompileTarget::Spirv,
+        "wgsl" => CompileTarget::Wgsl,
+        "hlsl" => CompileTarget::Hlsl,
+        "glsl" => CompileTarget::Glsl,
+        "metal" => CompileTarget::Metal,
+        "dxil" => CompileTarget::Dxil,
+        _ => {
+            return Err(syn::Error::new(
+                target.span(),
+                "expected one of `spirv`, `wgsl`, `hlsl`, `glsl`, `metal` or `dxil`",
+            ));
+        }
+    })
+}
+
+fn expand(input: &ShaderInput) -> syn::Result<TokenStream> {
+    let span = input.path.span();
+    let error = |message: String| syn::Error::new(span, message);
+
+    let manifest_dir = std::env::var_os("CARGO_MANIFEST_DIR")
+        .map(PathBuf::from)
+        .ok_or_else(|| error("CARGO_MANIFEST_DIR is not set".to_string()))?;
+    let path = manifest_dir.join(input.path.value());
+    let (Some(parent), Some(file_name), Some(stem)) =
+        (path.parent(), path.file_name(), path.file_stem())
+    else {
+        return Err(error(format!("`{}` is not a file", path.display())));
+    };
+
+    let mut reflector = Reflector::builder()
+        .search_path(parent.to_string_lossy().into_owned())
+        .target(input.target);
+    if let Some(profile) = &input.profile {
+        reflector = reflector.profile(profile.clone());
+    }
+    let program = reflector
+        .module(file_name.to_string_lossy().into_owned())
+        .compile()
+        .map_err(|e| error(e.to_string()))?;
+    let artifact = program.artifact().map_err(|e| error(e.to_string()))?;
+    let target = &artifact.targets[0];
+
+    let structs = RustCodegen::new()
+        .bytemuck(input.bytemuck)
+        .program(&target.reflection)
+        .generate()
+        .map_err(|e| error(e.to_string()))?
+        .parse::<TokenStream>()
+        .map_err(|e| error(e.to_string()))?;
+
+    let mut bindings = Vec::new();
+    let mut uniforms = Vec::new();
+    let mut specialization_constants = Vec::new();
+    let mut binding_names = ConstantNames::default();
+    let mut uniform_names = ConstantNames::default();
+    let mut specialization_constant_names = ConstantNames::default();
+    for variable in &target.reflection.variables {
+        let names = match &variable.reflection_type {
+            BoundParameter::Resource { .. } => &mut binding_names,
+            BoundParameter::Uniform { .. } => &mut uniform_names,
+            BoundParameter::SpecializationConstant(_) => &mut specialization_constant_names,
+            BoundParameter::PushConstants(_) => continue,
+        };
+        let name = names.insert(&variable.name, span)?;
+        match &variable.reflection_type {
+            BoundParameter::Resource {
+                binding_index,
+                binding_space,
+                ..
+            } => bindings.push(quote! {
+                pub const #name: Binding = Binding { index: #binding_index, space: #binding_space };
+            }),
+            BoundParameter::Uniform { uniform_offset, .. } => uniforms.push(quote! {
+                pub const #name: usize = #uniform_offset;
+            }),
+            BoundParameter::SpecializationConstant(constant) => {
+                let constant_id = constant.constant_id;
+                specialization_constants.push(quote! {
+                    pub const #name: u32 = #constant_id;
+                });
+            }
+            // Push constants have no binding; `RustCodegen` emits the block's struct.
+            BoundParameter::PushConstants(_) => {}
+        }
+    }
+
+    let mut entry_points = Vec::new();
+    let mut code = Vec::new();
+    let mut entry_point_names = ConstantNames::default();
+    for entry_point in &target.entry_points {
+        let name = entry_point_names.insert(&entry_point.name, span)?;
+        let entry_point_name = &entry_point.name;
+        let bytes = LitByteStr::new(&entry_point.code, Span::call_site());
+        entry_points.push(quote! { pub const #name: &str = #entry_point_name; });
+        code.push(quote! { pub const #name: &[u8] = #bytes; });
+    }
+
+    // `include_bytes!` makes cargo rebuild the crate whenever a shader file changes.
+    let dependencies = program
+        .dependency_files()
+        .iter()
+        .map(|file| resolve_dependency(file, parent))
+        .filter(|file| file.is_file())
+        .map(|file| {
+            let file = file.to_string_lossy().into_owned();
+            quote! { const _: &[u8] = include_bytes!(#file); }
+        });
+
+    let module = format_ident!("{}", rust_module_name(&stem.to_string_lossy()));
+    Ok(quote! {
+        pub mod #module {
+            #structs
+
+            pub mod bindings {
+                #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+                pub struct Binding {
+                    pub index: u32,
+                    pub space: u32,
+                }
+
+                #(#bindings)*
+            }
+
+            pub mod uniforms {
+                #(#uniforms)*
+            }
+
+            pub mod specialization_constants {
+                #(#specialization_constants)*
+            }
+
+            pub mod entry_points {
+                #(#entry_points)*
+            }
+
+            pub mod code {
+                #(#code)*
+            }
+
+            #(#dependencies)*
+        }
+    })
+}
+
+/// Slang reports dependencies relative to the working directory or the search path they were
+/// found in.
+fn resolve_dependency(file: &Path, search_path: &Path) -> PathBuf {
+    if file.is_absolute() || file.is_file() {
+        file.canonicalize().unwrap_or_else(|_| file.to_path_buf())
+    } else {
+        search_path.join(file)
+    }
+}
+
+/// The constants of one generated module, by name, with the shader symbol each was named after.
+#[derive(Default)]
+struct ConstantNames(HashMap<String, String>);
+
+impl ConstantNames {
+    /// The constant for `symbol`, or an error if another symbol already took its name.
+    fn insert(&mut self, symbol: &str, span: Span) -> syn::Result<Ident> {
+        let name = constant_name(symbol);
+        match self.0.insert(name.to_string(), symbol.to_string()) {
+            Some(previous) => Err(syn::Error::new(
+                span,
+                format!("`{previous}` and `{symbol}` both map to the constant `{name}`"),
+            )),
+            None => Ok(name),
+        }
+    }
+}
+
+/// `outputBuffer` becomes `OUTPUT_BUFFER`, `fillBuffer3` becomes `FILL_BUFFER3`.
+fn constant_name(name: &str) -> Ident {
+    let mut constant = String::new();
+    let mut previous_lower = false;
+    for c in name.chars() {
+        if c.is_uppercase() && previous_lower {
+            constant.push('_');
+        }
+        previous_lower = c.is_lowercase() || c.is_ascii_digit();
+        if c.is_alphanumeric() {
+            constant.extend(c.to_uppercase());
+        } else {
+            constant.push('_');
+        }
+    }
+    if constant.is_empty() || constant.starts_with(|c: char| c.is_ascii_digit()) {
+        constant.insert(0, '_');
+    }
+    Ident::new(&constant, Span::call_site())
+}
+
+fn rust_module_name(stem: &str) -> String {
+    let mut name = stem
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>();
+    if name.is_empty() || name.starts_with(|c: char| c.is_ascii_digit()) {
+        name.insert(0, '_');
+    }
+    name
+}
@@ -0,0 +1,58 @@
+// The generated structs carry `const` assertions on their size and field offsets, so including
+// them checks the layout `RustCodegen` computes against the one rustc gives the struct.
+
+slang_reflector_macros::include_shader!(
+    "../tests/layout.slang",
+    target = "wgsl",
+    profile = "spirv_1_6",
+    bytemuck = true
+);
+
+slang_reflector_macros::include_shader!(
+    "../tests/matrix.slang",
+    target = "wgsl",
+    profile = "spirv_1_6",
+    bytemuck = true
+);
+
+slang_reflector_macros::include_shader!(
+    "../tests/constant_buffer.slang",
+    target = "wgsl",
+    profile = "spirv_1_6",
+    bytemuck = true
+);
+
+slang_reflector_macros::include_shader!(
+    "../tests/push_constants.slang",
+    target = "spirv",
+    profile = "spirv_1_5",
+    bytemuck = true
+);
+
+#[test]
+fn generated_structs() {
+    let particle = layout::Particle {
+        radius: 2.0,
+        ..bytemuck::Zeroable::zeroed()
+    };
+    let bytes = bytemuck::bytes_of(&particle);
+    assert_eq!(bytes.len(), 48);
+    assert_eq!(bytes[12..16], 2.0f32.to_ne_bytes());
+
+    let camera: matrix::Camera = bytemuck::Zeroable::zeroed();
+    assert_eq!(bytemuck::bytes_of(&camera).len(), 112);
+
+    let params = constant_buffer::Params {
+        scale: 0.5,
+        ..bytemuck::Zeroable::zeroed()
+    };
+    let bytes = bytemuck::bytes_of(&params);
+    assert_eq!(bytes.len(), 32);
+    assert_eq!(bytes[16..20], 0.5f32.to_ne_bytes());
+
+    let push_constants = push_constants::Params {
+        tint: [1.0; 4],
+        offset: [0.0; 4],
+    };
+    assert_eq!(bytemuck::bytes_of(&push_constants).len(), 32);
+}
@@ -0,0 +1,26 @@
+slang_reflector_macros::include_shader!(
+    "../tests/str_attr.slang",
+    target = "wgsl",
+    profile = "spirv_1_6"
+);
+
+slang_reflector_macros::include_shader!("../tests/layout.slang", target = "spirv");
+
+#[test]
+fn include_shader() {
+    assert_eq!(str_attr::entry_points::FILL_BUFFER3, "fillBuffer3");
+    assert_eq!(
+        str_attr::bindings::OUTPUT_BUFFER,
+        str_attr::bindings::Binding { index: 0, space: 0 }
+    );
+    let code = std::str::from_utf8(str_attr::code::FILL_BUFFER3).unwrap();
+    assert!(code.contains("@compute"));
+
+    let uniform = str_attr::AnnotatedUniform { prop1: 7 };
+    assert_eq!(uniform.prop1, 7);
+    assert_eq!(std::mem::size_of::<str_attr::AnnotatedUniform>(), 4);
+
+    assert_eq!(layout::entry_points::MAIN, "main");
+    assert_eq!(std::mem::size_of::<layout::Particle>(), 48);
+    assert_eq!(&layout::code::MAIN[..4], &0x0723_0203u32.to_le_bytes());
+}
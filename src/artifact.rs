@@ -0,0 +1,50 @@
+use crate::{CompileTarget, ProgramReflection};
+
+/// Compiled code and reflection for every target of a program, produced by
+/// [`CompiledProgram::artifact`](crate::CompiledProgram::artifact). With the `derive-serde`
+/// feature it serializes into a single self-contained file that a runtime can load without
+/// linking libslang.
+#[cfg_attr(feature = "derive-serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+pub struct ShaderArtifact {
+    pub targets: Vec<TargetArtifact>,
+}
+
+impl ShaderArtifact {
+    /// Finds the first target compiled for `target`.
+    pub fn target(&self, target: CompileTarget) -> Option<&TargetArtifact> {
+        self.targets.iter().find(|t| t.target == target)
+    }
+}
+
+#[cfg_attr(feature = "derive-serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+pub struct TargetArtifact {
+    pub target: CompileTarget,
+    pub profile: Option<String>,
+    pub reflection: ProgramReflection,
+    /// Code for each entry point, in the same order as `reflection.entry_points`.
+    pub entry_points: Vec<EntryPointCode>,
+}
+
+impl TargetArtifact {
+    pub fn entry_point(&self, name: &str) -> Option<&EntryPointCode> {
+        self.entry_points.iter().find(|e| e.name == name)
+    }
+}
+
+#[cfg_attr(feature = "derive-serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+pub struct EntryPointCode {
+    pub name: String,
+    /// Target code as emitted by Slang: SPIR-V words for binary targets, source text for
+    /// WGSL, HLSL, GLSL and the like.
+    pub code: Vec<u8>,
+}
+
+impl EntryPointCode {
+    /// The code as text, for source targets such as WGSL or HLSL.
+    pub fn as_str(&self) -> Option<&str> {
+        std::str::from_utf8(&self.code).ok()
+    }
+}
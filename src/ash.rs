@@ -0,0 +1,287 @@
+//! Vulkan descriptor set layouts and push-constant ranges from a [`ProgramReflection`]
+//! reflected for [`CompileTarget::Spirv`](crate::CompileTarget::Spirv).
+//!
+//! Only plain `ash::vk` structs are produced; creating the layouts is left to the caller.
+//!
+//! # Stage visibility
+//!
+//! Reflection doesn't tell which entry point uses which global, so global resources and
+//! push-constant blocks are visible to the stages of every entry point of the pipeline. `uniform`
+//! entry point parameters, including push constants declared as entry point parameters, are only
+//! visible to the stage of their entry point.
+//!
+//! # Push-constant ranges
+//!
+//! Slang lays out every push-constant block from offset 0, and Vulkan rejects ranges that share
+//! a stage. Each stage therefore gets one range from 0 to the size of the largest block it sees,
+//! and stages with ranges of the same size share one.
+
+use std::fmt;
+
+use ::ash::vk;
+
+use crate::bindings::{Descriptor, pipeline_bindings};
+use crate::{BoundResource, DescriptorCount, ProgramReflection, ResourceAccess, Stage};
+
+/// The bindings of one descriptor set, sorted by binding.
+#[derive(Debug, Clone)]
+pub struct DescriptorSetLayoutReflection {
+    pub set: u32,
+    /// Bindings of runtime-sized arrays have a `descriptor_count` of zero, to be replaced by the
+    /// maximum number of descriptors the application binds.
+    pub bindings: Vec<vk::DescriptorSetLayoutBinding<'static>>,
+    /// Flags for `vk::DescriptorSetLayoutBindingFlagsCreateInfo`, one per binding. Runtime-sized
+    /// arrays are `VARIABLE_DESCRIPTOR_COUNT | PARTIALLY_BOUND`.
+    pub binding_flags: Vec<vk::DescriptorBindingFlags>,
+    /// Dotted path of the variable bound by each binding, e.g. `material.albedo`. The global
+    /// uniform buffer is named `$Globals`, the uniform buffer of an entry point
+    /// `<entry point>.$Params`.
+    pub names: Vec<String>,
+}
+
+impl DescriptorSetLayoutReflection {
+    /// The binding of the variable at `name`.
+    pub fn binding(&self, name: &str) -> Option<&vk::DescriptorSetLayoutBinding<'static>> {
+        let index = self.names.iter().position(|n| n == name)?;
+        Some(&self.bindings[index])
+    }
+
+    /// Whether any binding needs `vk::DescriptorSetLayoutBindingFlagsCreateInfo`.
+    pub fn has_binding_flags(&self) -> bool {
+        self.binding_flags.iter().any(|flags| !flags.is_empty())
+    }
+}
+
+/// Everything needed for a `vk::PipelineLayoutCreateInfo`.
+#[derive(Debug, Clone)]
+pub struct PipelineLayoutReflection {
+    /// Descriptor sets sorted by set index. Sets no resource uses are left out.
+    pub set_layouts: Vec<DescriptorSetLayoutReflection>,
+    pub push_constant_ranges: Vec<vk::PushConstantRange>,
+}
+
+/// Error produced when a resource has no Vulkan descriptor type.
+#[derive(Debug, Clone)]
+pub struct VulkanLayoutError {
+    /// Dotted path of the variable, e.g. `material.albedo`.
+    pub path: String,
+    pub message: String,
+}
+
+impl VulkanLayoutError {
+    fn new(path: &str, message: impl Into<String>) -> Self {
+        VulkanLayoutError {
+            path: path.to_string(),
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for VulkanLayoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "cannot bind `{}` with Vulkan: {}",
+            self.path, self.message
+        )
+    }
+}
+
+impl std::error::Error for VulkanLayoutError {}
+
+/// Pipeline layout for a pipeline made of every entry point of `program`.
+pub fn pipeline_layout(
+    program: &ProgramReflection,
+) -> Result<PipelineLayoutReflection, VulkanLayoutError> {
+    let entry_points = program
+        .entry_points
+        .iter()
+        .map(|e| e.name.as_str())
+        .collect::<Vec<_>>();
+    pipeline_layout_for(program, &entry_points)
+}
+
+/// Pipeline layout for a pipeline made of the named entry points, e.g. a vertex and a fragment
+/// shader. Stage visibility and the merging of push-constant ranges follow the rules in the
+/// [module documentation](crate::ash).
+pub fn pipeline_layout_for(
+    program: &ProgramReflection,
+    entry_points: &[&str],
+) -> Result<PipelineLayoutReflection, VulkanLayoutError> {
+    let bindings = pipeline_bindings(program, entry_points)
+        .map_err(|name| VulkanLayoutError::new(&name, "no entry point with this name"))?;
+
+    let mut set_layouts = Vec::new();
+    for descriptor in &bindings.descriptors {
+        let descriptor_type = match descriptor.descriptor {
+            Descriptor::Resource(resource) => descriptor_type(resource).ok_or_else(|| {
+                VulkanLayoutError::new(&descriptor.name, "resource has no descriptor type")
+            })?,
+            Descriptor::UniformBuffer { .. } => vk::DescriptorType::UNIFORM_BUFFER,
+            Descriptor::Counter => vk::DescriptorType::STORAGE_BUFFER,
+        };
+        let (descriptor_count, binding_flags) = match descriptor.count {
+            None => (1, vk::DescriptorBindingFlags::empty()),
+            Some(DescriptorCount::Fixed(count)) => {
+                (count as u32, vk::DescriptorBindingFlags::empty())
+            }
+            Some(DescriptorCount::Unbounded) => (
+                0,
+                vk::DescriptorBindingFlags::VARIABLE_DESCRIPTOR_COUNT
+                    | vk::DescriptorBindingFlags::PARTIALLY_BOUND,
+            ),
+        };
+        let binding = vk::DescriptorSetLayoutBinding::default()
+            .binding(descriptor.binding)
+            .descriptor_type(descriptor_type)
+            .descriptor_count(descriptor_count)
+            .stage_flags(stage_flags(&descriptor.stages));
+        insert_binding(
+            &mut set_layouts,
+            descriptor.set,
+            binding,
+            binding_flags,
+            &descriptor.name,
+        )?;
+    }
+
+    set_layouts.sort_by_key(|layout: &DescriptorSetLayoutReflection| layout.set);
+    for layout in &mut set_layouts {
+        let mut bindings = std::mem::take(&mut layout.bindings)
+            .into_iter()
+            .zip(std::mem::take(&mut layout.binding_flags))
+            .zip(std::mem::take(&mut layout.names))
+            .collect::<Vec<_>>();
+        bindings.sort_by_key(|((binding, _), _)| binding.binding);
+        for ((binding, flags), name) in bindings {
+            layout.bindings.push(binding);
+            layout.binding_flags.push(flags);
+            layout.names.push(name);
+        }
+    }
+
+    let push_constant_ranges = bindings
+        .push_constant_ranges()
+        .iter()
+        .map(|range| {
+            vk::PushConstantRange::default()
+                .stage_flags(stage_flags(&range.stages))
+                .offset(0)
+                .size(range.size as u32)
+        })
+        .collect();
+
+    Ok(PipelineLayoutReflection {
+        set_layouts,
+        push_constant_ranges,
+    })
+}
+
+/// Vulkan stage flag for a Slang stage.
+pub fn shader_stage_flags(stage: Stage) -> vk::ShaderStageFlags {
+    match stage {
+        Stage::Vertex => vk::ShaderStageFlags::VERTEX,
+        Stage::Hull => vk::ShaderStageFlags::TESSELLATION_CONTROL,
+        Stage::Domain => vk::ShaderStageFlags::TESSELLATION_EVALUATION,
+        Stage::Geometry => vk::ShaderStageFlags::GEOMETRY,
+        Stage::Fragment => vk::ShaderStageFlags::FRAGMENT,
+        Stage::Compute => vk::ShaderStageFlags::COMPUTE,
+        Stage::RayGeneration => vk::ShaderStageFlags::RAYGEN_KHR,
+        Stage::Intersection => vk::ShaderStageFlags::INTERSECTION_KHR,
+        Stage::AnyHit => vk::ShaderStageFlags::ANY_HIT_KHR,
+        Stage::ClosestHit => vk::ShaderStageFlags::CLOSEST_HIT_KHR,
+        Stage::Miss => vk::ShaderStageFlags::MISS_KHR,
+        Stage::Callable => vk::ShaderStageFlags::CALLABLE_KHR,
+        Stage::Mesh => vk::ShaderStageFlags::MESH_EXT,
+        Stage::Amplification => vk::ShaderStageFlags::TASK_EXT,
+        _ => vk::ShaderStageFlags::empty(),
+    }
+}
+
+/// Descriptor type of a resource; `None` for resources without one, such as parameter blocks,
+/// which take a descriptor set of their own.
+pub fn descriptor_type(resource: &BoundResource) -> Option<vk::DescriptorType> {
+    Some(match resource {
+        BoundResource::StructuredBuffer { .. }
+        | BoundResource::AppendConsumeBuffer { .. }
+        | BoundResource::RawBuffer { .. }
+        | BoundResource::TextureBuffer(_) => vk::DescriptorType::STORAGE_BUFFER,
+        BoundResource::ConstantBuffer(_) => vk::DescriptorType::UNIFORM_BUFFER,
+        BoundResource::Sampler { .. } => vk::DescriptorType::SAMPLER,
+        BoundResource::Texture {
+            resource_access: ResourceAccess::Read,
+            ..
+        } => vk::DescriptorType::SAMPLED_IMAGE,
+        BoundResource::Texture { .. } => vk::DescriptorType::STORAGE_IMAGE,
+        BoundResource::CombinedTextureSampler { .. } => vk::DescriptorType::COMBINED_IMAGE_SAMPLER,
+        BoundResource::TexelBuffer {
+            resource_access: ResourceAccess::Read,
+            ..
+        } => vk::DescriptorType::UNIFORM_TEXEL_BUFFER,
+        BoundResource::TexelBuffer { .. } => vk::DescriptorType::STORAGE_TEXEL_BUFFER,
+        BoundResource::Array { element, .. } => return descriptor_type(element),
+        // Takes whatever descriptor is written to it, which needs
+        // `VK_EXT_mutable_descriptor_type`.
+        BoundResource::DynamicResource => vk::DescriptorType::MUTABLE_EXT,
+        BoundResource::ParameterBlock(_) | BoundResource::FeedbackTexture { .. } => return None,
+    })
+}
+
+fn stage_flags(stages: &[Stage]) -> vk::ShaderStageFlags {
+    stages
+        .iter()
+        .fold(vk::ShaderStageFlags::empty(), |flags, &stage| {
+            flags | shader_stage_flags(stage)
+        })
+}
+
+/// Adds `binding` to `set`. A binding that is already there, e.g. a resource shared by two
+/// entry points, becomes visible to both stages.
+fn insert_binding(
+    set_layouts: &mut Vec<DescriptorSetLayoutReflection>,
+    set: u32,
+    binding: vk::DescriptorSetLayoutBinding<'static>,
+    binding_flags: vk::DescriptorBindingFlags,
+    path: &str,
+) -> Result<(), VulkanLayoutError> {
+    let index = match set_layouts.iter().position(|l| l.set == set) {
+        Some(index) => index,
+        None => {
+            set_layouts.push(DescriptorSetLayoutReflection {
+                set,
+                bindings: Vec::new(),
+                binding_flags: Vec::new(),
+                names: Vec::new(),
+            });
+            set_layouts.len() - 1
+        }
+    };
+    let layout = &mut set_layouts[index];
+    match layout
+        .bindings
+        .iter_mut()
+        .find(|b| b.binding == binding.binding)
+    {
+        Some(existing)
+            if existing.descriptor_type == binding.descriptor_type
+                && existing.descriptor_count == binding.descriptor_count =>
+        {
+            existing.stage_flags |= binding.stage_flags;
+        }
+        Some(_) => {
+            return Err(VulkanLayoutError::new(
+                path,
+                format!(
+                    "set {set} binding {} is already bound with a different type",
+                    binding.binding
+                ),
+            ));
+        }
+        None => {
+            layout.bindings.push(binding);
+            layout.binding_flags.push(binding_flags);
+            layout.names.push(path.to_string());
+        }
+    }
+    Ok(())
+}
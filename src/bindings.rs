@@ -0,0 +1,238 @@
+//! Flattening of a [`ProgramReflection`] into the descriptors and push constants of a pipeline,
+//! shared by the graphics API layout exports.
+
+use crate::{
+    BoundParameter, BoundResource, DescriptorCount, EntryPointUniformBuffer, ProgramReflection,
+    Stage, UniformBlock, VariableReflection,
+};
+
+/// A descriptor slot of a pipeline.
+pub(crate) struct DescriptorBinding<'a> {
+    /// Dotted path of the variable, e.g. `material.albedo`.
+    pub name: String,
+    pub set: u32,
+    pub binding: u32,
+    pub descriptor: Descriptor<'a>,
+    /// Descriptor count of resource arrays.
+    pub count: Option<DescriptorCount>,
+    pub stages: Vec<Stage>,
+}
+
+// Only `wgpu` needs the size of uniform buffers.
+#[cfg_attr(not(feature = "wgpu"), allow(dead_code))]
+pub(crate) enum Descriptor<'a> {
+    Resource(&'a BoundResource),
+    /// The uniform data of the global scope, of an entry point or of a parameter block.
+    UniformBuffer {
+        size: usize,
+    },
+    /// The counter buffer of an append or consume buffer.
+    Counter,
+}
+
+/// A push-constant block, at offset 0 of the push-constant range.
+pub(crate) struct PushConstantBinding {
+    pub size: usize,
+    pub stages: Vec<Stage>,
+}
+
+#[derive(Default)]
+pub(crate) struct PipelineBindings<'a> {
+    pub descriptors: Vec<DescriptorBinding<'a>>,
+    pub push_constants: Vec<PushConstantBinding>,
+}
+
+impl PipelineBindings<'_> {
+    /// The push-constant ranges of the pipeline, all at offset 0.
+    ///
+    /// Every push-constant block is laid out from offset 0, and Vulkan and wgpu both reject
+    /// ranges that share a stage. Each stage therefore gets the size of the largest block it
+    /// sees, and stages of the same size share a range.
+    pub fn push_constant_ranges(&self) -> Vec<PushConstantBinding> {
+        let mut stage_sizes: Vec<(Stage, usize)> = Vec::new();
+        for block in &self.push_constants {
+            for &stage in &block.stages {
+                match stage_sizes.iter_mut().find(|(s, _)| *s == stage) {
+                    Some((_, size)) => *size = (*size).max(block.size),
+                    None => stage_sizes.push((stage, block.size)),
+                }
+            }
+        }
+
+        let mut ranges: Vec<PushConstantBinding> = Vec::new();
+        for (stage, size) in stage_sizes {
+            match ranges.iter_mut().find(|r| r.size == size) {
+                Some(range) => range.stages.push(stage),
+                None => ranges.push(PushConstantBinding {
+                    size,
+                    stages: vec![stage],
+                }),
+            }
+        }
+        ranges
+    }
+}
+
+/// Collects the bindings of a pipeline made of the named entry points.
+///
+/// Global resources and push-constant blocks are used by the stages of all of those entry
+/// points, since reflection doesn't tell which entry point uses which global. `uniform` entry
+/// point parameters, including push constants declared as entry point parameters, are only used
+/// by the stage of their entry point. Resources inside a `ConstantBuffer` are bound relative to
+/// the buffer, those in a `ParameterBlock` relative to the block's set.
+///
+/// Fails with the name of an entry point that isn't in `program`.
+pub(crate) fn pipeline_bindings<'a>(
+    program: &'a ProgramReflection,
+    entry_points: &[&str],
+) -> Result<PipelineBindings<'a>, String> {
+    let selected = entry_points
+        .iter()
+        .map(|&name| {
+            program
+                .entry_points
+                .iter()
+                .find(|e| e.name == name)
+                .ok_or_else(|| name.to_string())
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let stages = selected.iter().map(|e| e.stage).collect::<Vec<_>>();
+
+    let mut bindings = PipelineBindings::default();
+    if let Some(buffer) = &program.global_uniform_buffer {
+        bindings.descriptors.push(DescriptorBinding {
+            name: "$Globals".to_string(),
+            set: buffer.binding_space,
+            binding: buffer.binding_index,
+            descriptor: Descriptor::UniformBuffer { size: buffer.size },
+            count: None,
+            stages: stages.clone(),
+        });
+    }
+    for variable in &program.variables {
+        bindings.add_variable(variable, 0, 0, &stages, "");
+    }
+    for entry_point in selected {
+        match entry_point.uniform_buffer {
+            Some(EntryPointUniformBuffer::ConstantBuffer {
+                binding_index,
+                binding_space,
+                size,
+            }) => bindings.descriptors.push(DescriptorBinding {
+                name: format!("{}.$Params", entry_point.name),
+                set: binding_space,
+                binding: binding_index,
+                descriptor: Descriptor::UniformBuffer { size },
+                count: None,
+                stages: vec![entry_point.stage],
+            }),
+            Some(EntryPointUniformBuffer::PushConstants { size }) => {
+                bindings.push_constants.push(PushConstantBinding {
+                    size,
+                    stages: vec![entry_point.stage],
+                })
+            }
+            None => {}
+        }
+        for variable in &entry_point.uniforms {
+            bindings.add_variable(variable, 0, 0, &[entry_point.stage], &entry_point.name);
+        }
+    }
+    Ok(bindings)
+}
+
+impl<'a> PipelineBindings<'a> {
+    fn add_variable(
+        &mut self,
+        variable: &'a VariableReflection,
+        base_binding: u32,
+        base_set: u32,
+        stages: &[Stage],
+        path: &str,
+    ) {
+        let (resource, binding_index, binding_space) = match &variable.reflection_type {
+            BoundParameter::Resource {
+                resource,
+                binding_index,
+                binding_space,
+                ..
+            } => (resource, binding_index, binding_space),
+            BoundParameter::PushConstants(block) => {
+                self.push_constants.push(PushConstantBinding {
+                    size: block.size,
+                    stages: stages.to_vec(),
+                });
+                return;
+            }
+            // Uniforms live in the global uniform buffer or in the block that contains them,
+            // specialization constants aren't bound at all.
+            BoundParameter::Uniform { .. } | BoundParameter::SpecializationConstant(_) => return,
+        };
+        let name = if path.is_empty() {
+            variable.name.clone()
+        } else {
+            format!("{path}.{}", variable.name)
+        };
+        let mut descriptor = DescriptorBinding {
+            name,
+            set: base_set + binding_space,
+            binding: base_binding + binding_index,
+            descriptor: Descriptor::Resource(resource),
+            count: None,
+            stages: stages.to_vec(),
+        };
+
+        match resource {
+            BoundResource::ParameterBlock(UniformBlock {
+                space,
+                uniform_size,
+                variables,
+            }) => {
+                // A parameter block gets a set of its own, with its uniform data at binding 0
+                // and its resources bound relative to the set.
+                let name = descriptor.name.clone();
+                if *uniform_size > 0 {
+                    self.descriptors.push(DescriptorBinding {
+                        set: *space,
+                        binding: 0,
+                        descriptor: Descriptor::UniformBuffer {
+                            size: *uniform_size,
+                        },
+                        ..descriptor
+                    });
+                }
+                for variable in variables {
+                    self.add_variable(variable, 0, *space, stages, &name);
+                }
+            }
+            BoundResource::ConstantBuffer(UniformBlock { variables, .. }) => {
+                let (binding, set) = (descriptor.binding, descriptor.set);
+                let name = descriptor.name.clone();
+                self.descriptors.push(descriptor);
+                for variable in variables {
+                    self.add_variable(variable, binding, set, stages, &name);
+                }
+            }
+            BoundResource::AppendConsumeBuffer {
+                counter: Some(counter),
+                ..
+            } => {
+                self.descriptors.push(DescriptorBinding {
+                    name: format!("{}.counter", descriptor.name),
+                    set: counter.binding_space,
+                    binding: counter.binding_index,
+                    descriptor: Descriptor::Counter,
+                    count: None,
+                    stages: stages.to_vec(),
+                });
+                self.descriptors.push(descriptor);
+            }
+            BoundResource::Array { element, count } => {
+                descriptor.descriptor = Descriptor::Resource(element);
+                descriptor.count = Some(*count);
+                self.descriptors.push(descriptor);
+            }
+            _ => self.descriptors.push(descriptor),
+        }
+    }
+}
@@ -0,0 +1,297 @@
+//! Compile-time reflection for `build.rs`.
+//!
+//! ```no_run
+//! // build.rs
+//! use slang_reflector::{CompileTarget, Reflector, build::Build};
+//!
+//! fn main() {
+//!     Build::new(
+//!         Reflector::builder()
+//!             .target(CompileTarget::Spirv)
+//!             .profile("spirv_1_5"),
+//!     )
+//!     .file("shaders/cube.slang")
+//!     .bytemuck(true)
+//!     .compile();
+//! }
+//! ```
+//!
+//! Outputs are named after the file's path relative to the crate root, with `_` between the
+//! directories, so that files of the same name in different directories don't overwrite each
+//! other. For `shaders/cube.slang` this writes to `OUT_DIR`:
+//!
+//! - `shaders_cube.rs`: the Rust structs generated by [`RustCodegen`] for the first target, for
+//!   `include!(concat!(env!("OUT_DIR"), "/shaders_cube.rs"))`,
+//! - `shaders_cube.json`: the serialized [`ShaderArtifact`], with reflection and code for every
+//!   target,
+//! - `shaders_cube.<entry point>.<ext>`: the code of every entry point, e.g.
+//!   `shaders_cube.vertexMain.spv`.
+
+use std::fmt;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+
+use crate::{
+    CodegenError, CompileTarget, ReflectorBuilder, ReflectorError, RustCodegen, ShaderArtifact,
+};
+
+/// Compiles `.slang` files from a build script and writes their reflection, Rust bindings and
+/// code into `OUT_DIR`.
+///
+/// Every file is compiled as a program of its own, with the targets, options, search paths and
+/// virtual files of the [`ReflectorBuilder`], and the file's directory as an extra search path.
+/// Modules added to the builder itself are ignored.
+pub struct Build {
+    reflector: ReflectorBuilder,
+    files: Vec<PathBuf>,
+    bytemuck: bool,
+    out_dir: Option<PathBuf>,
+    cargo_metadata: bool,
+}
+
+/// What [`Build::try_compile`] produced for one file.
+#[derive(Debug, Clone)]
+pub struct BuildOutput {
+    pub file: PathBuf,
+    pub artifact: ShaderArtifact,
+    /// Every file the program was compiled from, including `file` itself.
+    pub dependency_files: Vec<PathBuf>,
+    /// Files written to the output directory.
+    pub outputs: Vec<PathBuf>,
+}
+
+impl Build {
+    pub fn new(reflector: ReflectorBuilder) -> Self {
+        Build {
+            reflector,
+            files: Vec::new(),
+            bytemuck: false,
+            out_dir: None,
+            cargo_metadata: true,
+        }
+    }
+
+    /// Adds a `.slang` file to compile. The file is loaded by its full path, and its directory
+    /// is searched first when compiling it, so that sibling modules can be imported.
+    pub fn file(mut self, path: impl AsRef<Path>) -> Self {
+        self.files.push(path.as_ref().to_path_buf());
+        self
+    }
+
+    pub fn files<P: AsRef<Path>>(self, paths: impl IntoIterator<Item = P>) -> Self {
+        paths.into_iter().fold(self, |build, path| build.file(path))
+    }
+
+    /// Derives `bytemuck::Pod` and `bytemuck::Zeroable` on the generated structs.
+    pub fn bytemuck(mut self, enabled: bool) -> Self {
+        self.bytemuck = enabled;
+        self
+    }
+
+    /// Writes into `out_dir` instead of the `OUT_DIR` cargo sets for build scripts.
+    pub fn out_dir(mut self, out_dir: impl Into<PathBuf>) -> Self {
+        self.out_dir = Some(out_dir.into());
+        self
+    }
+
+    /// Whether to print `cargo:rerun-if-changed` for every dependency. Enabled by default.
+    pub fn cargo_metadata(mut self, enabled: bool) -> Self {
+        self.cargo_metadata = enabled;
+        self
+    }
+
+    /// Compiles every file, panicking with the error so that cargo reports it as a build
+    /// failure.
+    pub fn compile(&self) -> Vec<BuildOutput> {
+        match self.try_compile() {
+            Ok(outputs) => outputs,
+            Err(error) => panic!("{error}"),
+        }
+    }
+
+    pub fn try_compile(&self) -> Result<Vec<BuildOutput>, BuildError> {
+        let out_dir = match &self.out_dir {
+            Some(out_dir) => out_dir.clone(),
+            None => std::env::var_os("OUT_DIR")
+                .map(PathBuf::from)
+                .ok_or(BuildError::OutDir)?,
+        };
+        std::fs::create_dir_all(&out_dir).map_err(|e| BuildError::io(&out_dir, e))?;
+        // Set by cargo for build scripts and tests; otherwise paths are relative to the working
+        // directory.
+        let crate_root = match std::env::var_os("CARGO_MANIFEST_DIR") {
+            Some(crate_root) => PathBuf::from(crate_root),
+            None => std::env::current_dir().map_err(|e| BuildError::io(Path::new("."), e))?,
+        };
+
+        self.files
+            .iter()
+            .map(|file| self.compile_file(file, &crate_root, &out_dir))
+            .collect()
+    }
+
+    fn compile_file(
+        &self,
+        file: &Path,
+        crate_root: &Path,
+        out_dir: &Path,
+    ) -> Result<BuildOutput, BuildError> {
+        if self.cargo_metadata {
+            // Printed before compiling so that fixing a broken shader triggers a rebuild.
+            println!("cargo:rerun-if-changed={}", file.display());
+        }
+
+        // Loaded by path, so that a file of the same name on an earlier search path can't
+        // take its place.
+        let absolute_file = std::path::absolute(file).map_err(|e| BuildError::io(file, e))?;
+        let module = absolute_file.to_string_lossy().into_owned();
+        let search_paths = absolute_file
+            .parent()
+            .map(|parent| parent.to_string_lossy().into_owned())
+            .into_iter()
+            .collect::<Vec<_>>();
+        let name = output_name(&absolute_file, crate_root);
+
+        let reflector_error = |error| BuildError::Reflector {
+            file: file.to_path_buf(),
+            error,
+        };
+        let program = self
+            .reflector
+            .compile_modules(&[module], &search_paths)
+            .map_err(reflector_error)?;
+        let dependency_files = program.dependency_files().to_vec();
+        if self.cargo_metadata {
+            for dependency in &dependency_files {
+                println!("cargo:rerun-if-changed={}", dependency.display());
+            }
+        }
+        let artifact = program.artifact().map_err(reflector_error)?;
+
+        // Layouts differ between targets, the bindings follow the first one.
+        let bindings = RustCodegen::new()
+            .bytemuck(self.bytemuck)
+            .program(&artifact.targets[0].reflection)
+            .generate()
+            .map_err(|error| BuildError::Codegen {
+                file: file.to_path_buf(),
+                error,
+            })?;
+
+        let mut outputs = Vec::new();
+        let mut write = |file_name: String, contents: &[u8]| {
+            let path = out_dir.join(file_name);
+            std::fs::write(&path, contents).map_err(|e| BuildError::io(&path, e))?;
+            outputs.push(path);
+            Ok::<_, BuildError>(())
+        };
+        write(format!("{name}.rs"), bindings.as_bytes())?;
+        let json = serde_json::to_vec(&artifact).map_err(BuildError::Serialize)?;
+        write(format!("{name}.json"), &json)?;
+        for target in &artifact.targets {
+            let extension = target_extension(target.target);
+            for entry_point in &target.entry_points {
+                write(
+                    format!("{name}.{}.{extension}", entry_point.name),
+                    &entry_point.code,
+                )?;
+            }
+        }
+
+        Ok(BuildOutput {
+            file: file.to_path_buf(),
+            artifact,
+            dependency_files,
+            outputs,
+        })
+    }
+}
+
+/// Name of the outputs for `file`: its path relative to `crate_root` without the extension, with
+/// `_` between the components, e.g. `shaders_post_blur` for `shaders/post/blur.slang`. Files
+/// outside the crate are named after their absolute path.
+fn output_name(file: &Path, crate_root: &Path) -> String {
+    let crate_root = std::path::absolute(crate_root).unwrap_or_else(|_| crate_root.to_path_buf());
+    file.strip_prefix(&crate_root)
+        .unwrap_or(file)
+        .with_extension("")
+        .components()
+        .filter_map(|component| match component {
+            Component::Normal(name) => Some(name.to_string_lossy().into_owned()),
+            _ => None,
+        })
+        .collect::<Vec<_>>()
+        .join("_")
+}
+
+/// File extension for code compiled for `target`.
+fn target_extension(target: CompileTarget) -> &'static str {
+    match target {
+        CompileTarget::Spirv => "spv",
+        CompileTarget::SpirvAsm => "spvasm",
+        CompileTarget::Wgsl => "wgsl",
+        CompileTarget::Hlsl => "hlsl",
+        CompileTarget::Glsl => "glsl",
+        CompileTarget::Dxil => "dxil",
+        CompileTarget::Dxbc => "dxbc",
+        CompileTarget::Metal => "metal",
+        CompileTarget::MetalLib => "metallib",
+        CompileTarget::CudaSource => "cu",
+        CompileTarget::Ptx => "ptx",
+        _ => "bin",
+    }
+}
+
+/// Error produced by [`Build::try_compile`].
+#[derive(Debug)]
+pub enum BuildError {
+    /// `OUT_DIR` is not set, i.e. not running from a build script, and no output directory was
+    /// given.
+    OutDir,
+    Reflector {
+        file: PathBuf,
+        error: ReflectorError,
+    },
+    Codegen {
+        file: PathBuf,
+        error: CodegenError,
+    },
+    Io {
+        path: PathBuf,
+        error: io::Error,
+    },
+    Serialize(serde_json::Error),
+}
+
+impl BuildError {
+    fn io(path: &Path, error: io::Error) -> Self {
+        BuildError::Io {
+            path: path.to_path_buf(),
+            error,
+        }
+    }
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BuildError::OutDir => write!(f, "OUT_DIR is not set"),
+            BuildError::Reflector { file, error } => write!(f, "{}: {error}", file.display()),
+            BuildError::Codegen { file, error } => write!(f, "{}: {error}", file.display()),
+            BuildError::Io { path, error } => write!(f, "{}: {error}", path.display()),
+            BuildError::Serialize(error) => write!(f, "failed to serialize artifact: {error}"),
+        }
+    }
+}
+
+impl std::error::Error for BuildError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            BuildError::OutDir => None,
+            BuildError::Reflector { error, .. } => Some(error),
+            BuildError::Codegen { error, .. } => Some(error),
+            BuildError::Io { error, .. } => Some(error),
+            BuildError::Serialize(error) => Some(error),
+        }
+    }
+}
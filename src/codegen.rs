@@ -0,0 +1,475 @@
+use std::fmt::{self, Write};
+
+use crate::{
+    BoundParameter, BoundResource, MatrixLayout, ProgramReflection, ScalarType,
+    StructFieldReflection, TypeLayoutReflection, UserAttributeParameter, VariableReflection,
+    VariableReflectionType,
+};
+
+/// User attribute whose string argument is copied onto the generated Rust item.
+const ANNOTATION_ATTRIBUTE: &str = "playground_ANNOTATION";
+
+/// Generates `#[repr(C)]` Rust structs that match the layout of reflected Slang structs.
+///
+/// Every gap Slang leaves between fields, and after the last field up to the array stride, is
+/// filled with an explicit `_padN: [u8; N]` field, so the generated types have no implicit
+/// padding and can derive `bytemuck::Pod`. Vectors become arrays, matrices become arrays of
+/// their row or column vectors widened to the matrix stride.
+///
+/// `playground::ANNOTATION("...")` on a struct is emitted as an attribute when its argument
+/// looks like one (`doc = "..."`, `derive(Default)`) and as a doc comment otherwise.
+///
+/// ```no_run
+/// use slang_reflector::{CompileTarget, Reflector, RustCodegen};
+///
+/// let reflection = Reflector::builder()
+///     .search_path("shaders")
+///     .target(CompileTarget::Wgsl)
+///     .module("cube.slang")
+///     .reflect()
+///     .unwrap();
+/// let source = RustCodegen::new()
+///     .bytemuck(true)
+///     .program(&reflection)
+///     .generate()
+///     .unwrap();
+/// ```
+#[derive(Debug, Clone)]
+pub struct RustCodegen {
+    types: Vec<VariableReflectionType>,
+    bytemuck: bool,
+    layout_assertions: bool,
+}
+
+impl Default for RustCodegen {
+    fn default() -> Self {
+        RustCodegen {
+            types: Vec::new(),
+            bytemuck: false,
+            layout_assertions: true,
+        }
+    }
+}
+
+impl RustCodegen {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Derives `bytemuck::Pod` and `bytemuck::Zeroable` on every generated struct.
+    ///
+    /// The crate compiling the generated code needs `bytemuck` with the `derive` and
+    /// `min_const_generics` features, since padding arrays can have any length.
+    pub fn bytemuck(mut self, enabled: bool) -> Self {
+        self.bytemuck = enabled;
+        self
+    }
+
+    /// Emits `const` assertions on the size of every struct and the offset of every field.
+    /// Enabled by default.
+    pub fn layout_assertions(mut self, enabled: bool) -> Self {
+        self.layout_assertions = enabled;
+        self
+    }
+
+    /// Adds a type. Structs are generated along with every struct they contain; other types
+    /// only contribute the structs nested in them.
+    pub fn add_type(mut self, reflection_type: &VariableReflectionType) -> Self {
+        self.types.push(reflection_type.clone());
+        self
+    }
+
+    /// Adds every struct used by the program's uniforms, buffers and parameter blocks.
+    pub fn program(mut self, program: &ProgramReflection) -> Self {
+        let variables = program
+            .variables
+            .iter()
+            .chain(program.entry_points.iter().flat_map(|e| &e.uniforms));
+        for variable in variables {
+            collect_variable_types(variable, &mut self.types);
+        }
+        self
+    }
+
+    /// Generates Rust source for every struct that was added.
+    pub fn generate(&self) -> Result<String, CodegenError> {
+        let mut structs = Vec::new();
+        for reflection_type in &self.types {
+            collect_structs(reflection_type, &mut structs)?;
+        }
+
+        let mut source = String::new();
+        for (index, reflection_type) in structs.iter().enumerate() {
+            if index > 0 {
+                source.push('\n');
+            }
+            self.write_struct(&mut source, reflection_type)?;
+        }
+        Ok(source)
+    }
+
+    fn write_struct(
+        &self,
+        source: &mut String,
+        reflection_type: &VariableReflectionType,
+    ) -> Result<(), CodegenError> {
+        let VariableReflectionType::Struct {
+            name,
+            props,
+            user_attributes,
+            layout,
+        } = reflection_type
+        else {
+            unreachable!("only structs are collected");
+        };
+        let struct_name = rust_identifier(name);
+
+        let mut fields = props.iter().collect::<Vec<_>>();
+        fields.sort_by_key(|field| field.offset);
+
+        let mut body = String::new();
+        let mut cursor = 0;
+        let mut padding_count = 0;
+        for field in &fields {
+            let path = format!("{name}.{}", field.name);
+            if field.offset < cursor {
+                return Err(CodegenError::new(
+                    path,
+                    format!("field overlaps the previous field at offset {cursor}"),
+                ));
+            }
+            if field.offset > cursor {
+                write_padding(&mut body, &mut padding_count, field.offset - cursor);
+            }
+            let (rust_type, size) = rust_type(&field.reflection_type, &field.layout, &path)?;
+            writeln!(
+                body,
+                "    pub {}: {rust_type},",
+                rust_identifier(&field.name)
+            )
+            .unwrap();
+            cursor = field.offset + size;
+        }
+        if cursor > layout.stride {
+            return Err(CodegenError::new(
+                name,
+                format!("fields end at {cursor}, past the stride {}", layout.stride),
+            ));
+        }
+        if cursor < layout.stride {
+            write_padding(&mut body, &mut padding_count, layout.stride - cursor);
+        }
+
+        for attribute in user_attributes
+            .iter()
+            .filter(|attribute| attribute.name == ANNOTATION_ATTRIBUTE)
+        {
+            for parameter in &attribute.parameters {
+                if let UserAttributeParameter::String(annotation) = parameter {
+                    write_annotation(source, annotation);
+                }
+            }
+        }
+        source.push_str("#[repr(C)]\n");
+        if self.bytemuck {
+            source.push_str(
+                "#[derive(Debug, Clone, Copy, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]\n",
+            );
+        } else {
+            source.push_str("#[derive(Debug, Clone, Copy, PartialEq)]\n");
+        }
+        writeln!(source, "pub struct {struct_name} {{").unwrap();
+        source.push_str(&body);
+        source.push_str("}\n");
+
+        if self.layout_assertions {
+            source.push('\n');
+            writeln!(
+                source,
+                "const _: () = assert!(::core::mem::size_of::<{struct_name}>() == {});",
+                layout.stride
+            )
+            .unwrap();
+            for field in &fields {
+                writeln!(
+                    source,
+                    "const _: () = assert!(::core::mem::offset_of!({struct_name}, {}) == {});",
+                    rust_identifier(&field.name),
+                    field.offset
+                )
+                .unwrap();
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Error produced when a reflected struct can't be expressed as a `#[repr(C)]` Rust struct.
+#[derive(Debug, Clone)]
+pub struct CodegenError {
+    /// Dotted path of the struct or field, e.g. `Particle.position`.
+    pub path: String,
+    pub message: String,
+}
+
+impl CodegenError {
+    fn new(path: impl Into<String>, message: impl Into<String>) -> Self {
+        CodegenError {
+            path: path.into(),
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for CodegenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "cannot generate `{}`: {}", self.path, self.message)
+    }
+}
+
+impl std::error::Error for CodegenError {}
+
+fn collect_variable_types(variable: &VariableReflection, types: &mut Vec<VariableReflectionType>) {
+    match &variable.reflection_type {
+        BoundParameter::Uniform {
+            resource_result, ..
+        } => types.push(resource_result.clone()),
+        BoundParameter::Resource { resource, .. } => collect_resource_types(resource, types),
+        BoundParameter::PushConstants(block) => types.push(block.element_type.clone()),
+        BoundParameter::SpecializationConstant(_) => {}
+    }
+}
+
+fn collect_resource_types(resource: &BoundResource, types: &mut Vec<VariableReflectionType>) {
+    match resource {
+        BoundResource::StructuredBuffer {
+            resource_result, ..
+        }
+        | BoundResource::AppendConsumeBuffer {
+            resource_result, ..
+        } => types.push(resource_result.clone()),
+        BoundResource::ConstantBuffer(block)
+        | BoundResource::ParameterBlock(block)
+        | BoundResource::TextureBuffer(block) => {
+            types.push(block.element_type.clone());
+            // Buffers nested in the block have structs of their own.
+            for variable in &block.variables {
+                collect_variable_types(variable, types);
+            }
+        }
+        BoundResource::Array { element, .. } => collect_resource_types(element, types),
+        _ => {}
+    }
+}
+
+/// Collects `reflection_type` and the structs nested in it, contained structs first. A struct
+/// that shows up again with a different layout, e.g. in both a constant buffer and a structured
+/// buffer, can't share one Rust type and is an error.
+fn collect_structs(
+    reflection_type: &VariableReflectionType,
+    structs: &mut Vec<VariableReflectionType>,
+) -> Result<(), CodegenError> {
+    match reflection_type {
+        VariableReflectionType::Struct {
+            name,
+            props,
+            layout,
+            ..
+        } => {
+            if let Some(existing) = structs.iter().find(|s| struct_name(s) == name) {
+                if same_struct_layout(existing, props, layout) {
+                    return Ok(());
+                }
+                return Err(CodegenError::new(
+                    name,
+                    "struct is used with more than one layout",
+                ));
+            }
+            for field in props {
+                collect_structs(&field.reflection_type, structs)?;
+            }
+            structs.push(reflection_type.clone());
+        }
+        VariableReflectionType::Array { element, .. } => collect_structs(element, structs)?,
+        _ => {}
+    }
+    Ok(())
+}
+
+fn struct_name(reflection_type: &VariableReflectionType) -> &str {
+    match reflection_type {
+        VariableReflectionType::Struct { name, .. } => name,
+        _ => "",
+    }
+}
+
+fn same_struct_layout(
+    existing: &VariableReflectionType,
+    props: &[StructFieldReflection],
+    layout: &TypeLayoutReflection,
+) -> bool {
+    let VariableReflectionType::Struct {
+        props: existing_props,
+        layout: existing_layout,
+        ..
+    } = existing
+    else {
+        return false;
+    };
+    existing_layout == layout
+        && existing_props.len() == props.len()
+        && existing_props
+            .iter()
+            .zip(props)
+            .all(|(a, b)| a.name == b.name && a.offset == b.offset && a.layout == b.layout)
+}
+
+/// Rust type for a field and the number of bytes it occupies.
+fn rust_type(
+    reflection_type: &VariableReflectionType,
+    layout: &TypeLayoutReflection,
+    path: &str,
+) -> Result<(String, usize), CodegenError> {
+    Ok(match reflection_type {
+        VariableReflectionType::Scalar(scalar) => {
+            let (name, size) = rust_scalar(*scalar, path)?;
+            (name.to_string(), size)
+        }
+        VariableReflectionType::Vector(scalar, count) => {
+            let (name, size) = rust_scalar(*scalar, path)?;
+            (format!("[{name}; {count}]"), size * count)
+        }
+        VariableReflectionType::Matrix {
+            scalar,
+            rows,
+            columns,
+            layout: matrix_layout,
+            stride,
+        } => {
+            let (name, size) = rust_scalar(*scalar, path)?;
+            let (vector_count, vector_len) = match matrix_layout {
+                MatrixLayout::RowMajor => (*rows, *columns),
+                MatrixLayout::ColumnMajor => (*columns, *rows),
+            };
+            let lanes = widened_lanes(*stride, size, vector_len, path)?;
+            (
+                format!("[[{name}; {lanes}]; {vector_count}]"),
+                stride * vector_count,
+            )
+        }
+        VariableReflectionType::Array {
+            element,
+            count,
+            element_layout,
+        } => {
+            let (element_type, element_size) = rust_type(element, element_layout, path)?;
+            let stride = element_layout.stride;
+            if element_size == stride {
+                (format!("[{element_type}; {count}]"), stride * count)
+            } else {
+                // Scalars and vectors in std140-like arrays are padded to the stride.
+                let (scalar, vector_len) = match element.as_ref() {
+                    VariableReflectionType::Scalar(scalar) => (*scalar, 1),
+                    VariableReflectionType::Vector(scalar, len) => (*scalar, *len),
+                    _ => {
+                        return Err(CodegenError::new(
+                            path,
+                            format!("element of {element_size} bytes doesn't fill stride {stride}"),
+                        ));
+                    }
+                };
+                let (name, size) = rust_scalar(scalar, path)?;
+                let lanes = widened_lanes(stride, size, vector_len, path)?;
+                (format!("[[{name}; {lanes}]; {count}]"), stride * count)
+            }
+        }
+        VariableReflectionType::Struct { name, .. } => (rust_identifier(name), layout.stride),
+    })
+}
+
+/// Number of scalars a vector of `vector_len` is widened to so it fills `stride` bytes.
+fn widened_lanes(
+    stride: usize,
+    scalar_size: usize,
+    vector_len: usize,
+    path: &str,
+) -> Result<usize, CodegenError> {
+    if stride % scalar_size != 0 || stride / scalar_size < vector_len {
+        return Err(CodegenError::new(
+            path,
+            format!("stride {stride} doesn't hold {vector_len} scalars of {scalar_size} bytes"),
+        ));
+    }
+    Ok(stride / scalar_size)
+}
+
+fn rust_scalar(scalar: ScalarType, path: &str) -> Result<(&'static str, usize), CodegenError> {
+    Ok(match scalar {
+        // `bool` occupies 4 bytes in every buffer layout.
+        ScalarType::Bool => ("u32", 4),
+        ScalarType::Int8 => ("i8", 1),
+        ScalarType::Uint8 => ("u8", 1),
+        ScalarType::Int16 => ("i16", 2),
+        ScalarType::Uint16 => ("u16", 2),
+        ScalarType::Int32 => ("i32", 4),
+        ScalarType::Uint32 => ("u32", 4),
+        ScalarType::Int64 | ScalarType::Intptr => ("i64", 8),
+        ScalarType::Uint64 | ScalarType::Uintptr => ("u64", 8),
+        // Stable Rust has no `f16`; keep the raw bits.
+        ScalarType::Float16 => ("u16", 2),
+        ScalarType::Float32 => ("f32", 4),
+        ScalarType::Float64 => ("f64", 8),
+        _ => {
+            return Err(CodegenError::new(
+                path,
+                format!("scalar type {scalar:?} has no Rust equivalent"),
+            ));
+        }
+    })
+}
+
+fn write_padding(body: &mut String, padding_count: &mut usize, size: usize) {
+    writeln!(body, "    pub _pad{padding_count}: [u8; {size}],").unwrap();
+    *padding_count += 1;
+}
+
+fn write_annotation(source: &mut String, annotation: &str) {
+    let annotation = annotation.trim();
+    let path_len = annotation
+        .find(|c: char| !(c.is_alphanumeric() || c == '_' || c == ':'))
+        .unwrap_or(annotation.len());
+    let rest = annotation[path_len..].trim_start();
+    if path_len > 0 && (rest.is_empty() || rest.starts_with('=') || rest.starts_with('(')) {
+        writeln!(source, "#[{annotation}]").unwrap();
+    } else {
+        for line in annotation.lines() {
+            writeln!(source, "/// {}", line.trim_end()).unwrap();
+        }
+    }
+}
+
+fn rust_identifier(name: &str) -> String {
+    const KEYWORDS: &[&str] = &[
+        "as", "async", "await", "box", "break", "const", "continue", "dyn", "else", "enum",
+        "extern", "false", "fn", "for", "gen", "if", "impl", "in", "let", "loop", "match", "mod",
+        "move", "mut", "pub", "ref", "return", "static", "struct", "trait", "true", "try", "type",
+        "unsafe", "use", "where", "while", "yield",
+    ];
+    let mut identifier = name
+        .chars()
+        .map(|c| {
+            if c.is_alphanumeric() || c == '_' {
+                c
+            } else {
+                '_'
+            }
+        })
+        .collect::<String>();
+    if identifier.is_empty() || identifier.starts_with(|c: char| c.is_ascii_digit()) {
+        identifier.insert(0, '_');
+    }
+    if KEYWORDS.contains(&identifier.as_str()) {
+        identifier.insert_str(0, "r#");
+    }
+    identifier
+}
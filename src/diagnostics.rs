@@ -0,0 +1,199 @@
+use std::fmt;
+use std::ptr::NonNull;
+
+use shader_slang::Blob;
+use shader_slang::sys::ISlangBlob;
+
+#[cfg_attr(feature = "derive-serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Note,
+    Warning,
+    Error,
+    Fatal,
+    Internal,
+}
+
+impl Severity {
+    fn parse_prefix(text: &str) -> Option<(Severity, &str)> {
+        [
+            ("fatal error", Severity::Fatal),
+            ("internal error", Severity::Internal),
+            ("error", Severity::Error),
+            ("warning", Severity::Warning),
+            ("note", Severity::Note),
+        ]
+        .into_iter()
+        .find_map(|(keyword, severity)| text.strip_prefix(keyword).map(|rest| (severity, rest)))
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Severity::Note => "note",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+            Severity::Fatal => "fatal error",
+            Severity::Internal => "internal error",
+        })
+    }
+}
+
+/// A single message from Slang's diagnostic output, e.g.
+/// `shaders/cube.slang(12): error 30015: undefined identifier 'foo'.`
+#[cfg_attr(feature = "derive-serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub file: Option<String>,
+    /// 1-based line.
+    pub line: Option<u32>,
+    /// 1-based column, taken from the caret Slang prints under the echoed source line.
+    pub column: Option<u32>,
+    pub code: Option<u32>,
+    pub message: String,
+}
+
+impl Diagnostic {
+    /// Parses Slang's diagnostic text. Output that doesn't follow Slang's diagnostic format
+    /// becomes a single error without a location, so no message is lost.
+    pub fn parse(text: &str) -> Vec<Diagnostic> {
+        let mut diagnostics = Vec::new();
+        let mut lines = text.lines().peekable();
+        while let Some(line) = lines.next() {
+            let Some(mut diagnostic) = parse_header(line) else {
+                continue;
+            };
+            // Slang echoes the offending source line followed by a caret line pointing at the
+            // column.
+            if diagnostic.line.is_some() && lines.peek().is_some_and(|l| parse_header(l).is_none())
+            {
+                let _source = lines.next();
+                if let Some(caret) = lines.next_if(|l| is_caret_line(l)) {
+                    diagnostic.column = diagnostic
+                        .column
+                        .or_else(|| caret.find('^').map(|i| i as u32 + 1));
+                }
+            }
+            diagnostics.push(diagnostic);
+        }
+
+        if diagnostics.is_empty() && !text.trim().is_empty() {
+            diagnostics.push(Diagnostic {
+                severity: Severity::Error,
+                file: None,
+                line: None,
+                column: None,
+                code: None,
+                message: text.trim().to_string(),
+            });
+        }
+        diagnostics
+    }
+
+    pub fn is_error(&self) -> bool {
+        self.severity >= Severity::Error
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(file) = &self.file {
+            write!(f, "{file}")?;
+        }
+        match (self.line, self.column) {
+            (Some(line), Some(column)) => write!(f, "({line},{column}): ")?,
+            (Some(line), None) => write!(f, "({line}): ")?,
+            _ if self.file.is_some() => write!(f, ": ")?,
+            _ => {}
+        }
+        write!(f, "{}", self.severity)?;
+        if let Some(code) = self.code {
+            write!(f, " {code}")?;
+        }
+        write!(f, ": {}", self.message)
+    }
+}
+
+/// Converts the diagnostics carried by a failed Slang call.
+pub(crate) fn diagnostics_from_error(error: &shader_slang::Error) -> Vec<Diagnostic> {
+    Diagnostic::parse(&error.to_string())
+}
+
+/// Parses and releases the diagnostics blob a raw Slang call returned through its
+/// `outDiagnostics` parameter, which is null when there is nothing to report.
+///
+/// # Safety
+///
+/// `blob` must be null or point to a live `ISlangBlob` holding a reference the caller owns.
+/// That reference is released here.
+pub(crate) unsafe fn diagnostics_from_blob(blob: *mut ISlangBlob) -> Vec<Diagnostic> {
+    // `Blob` must be nothing but the interface pointer for the transmute below.
+    const _: () = assert!(size_of::<Blob>() == size_of::<*mut ISlangBlob>());
+
+    let Some(blob) = NonNull::new(blob) else {
+        return Vec::new();
+    };
+    // SAFETY: `Blob` is a transparent wrapper around the interface pointer. It takes over the
+    // reference the caller handed in and releases it when dropped.
+    let blob = unsafe { std::mem::transmute::<NonNull<ISlangBlob>, Blob>(blob) };
+    Diagnostic::parse(&String::from_utf8_lossy(blob.as_slice()))
+}
+
+/// Parses `file(line[,column]): severity [code]: message`, with the location being optional.
+///
+/// The header is found from the left by its severity keyword, which starts the line or follows
+/// the first `): ` that is followed by one, so parentheses in the path or in the message don't
+/// matter. A location that doesn't parse is dropped rather than the whole diagnostic.
+fn parse_header(line: &str) -> Option<Diagnostic> {
+    let (start, (severity, code, message)) = std::iter::once(0)
+        .chain(line.match_indices("): ").map(|(index, _)| index + 3))
+        .find_map(|start| Some((start, parse_severity(&line[start..])?)))?;
+    let (file, line_number, column) = match start {
+        0 => (None, None, None),
+        _ => parse_location(&line[..start - 3]).unwrap_or_default(),
+    };
+    Some(Diagnostic {
+        severity,
+        file,
+        line: line_number,
+        column,
+        code,
+        message: message.trim().to_string(),
+    })
+}
+
+/// Parses `severity [code]: message`.
+fn parse_severity(text: &str) -> Option<(Severity, Option<u32>, &str)> {
+    let (severity, rest) = Severity::parse_prefix(text)?;
+    let (code, message) = rest.split_once(':')?;
+    let code = code.trim();
+    let code = if code.is_empty() {
+        None
+    } else {
+        Some(code.parse().ok()?)
+    };
+    Some((severity, code, message))
+}
+
+/// Parses `file(line[,column]`, the location without its closing parenthesis.
+fn parse_location(location: &str) -> Option<(Option<String>, Option<u32>, Option<u32>)> {
+    let (file, numbers) = location.rsplit_once('(')?;
+    let mut numbers = numbers.split(',').map(|n| n.trim().parse::<u32>());
+    let line_number = numbers.next()?.ok()?;
+    let column = numbers.next().transpose().ok()?;
+    if numbers.next().is_some() {
+        return None;
+    }
+    Some((
+        (!file.is_empty()).then(|| file.to_string()),
+        // Slang reports `(0)` for diagnostics without a source location.
+        (line_number != 0).then_some(line_number),
+        column,
+    ))
+}
+
+fn is_caret_line(line: &str) -> bool {
+    line.contains('^') && line.chars().all(|c| matches!(c, ' ' | '\t' | '^' | '~'))
+}
@@ -0,0 +1,214 @@
+//! Copies of the Slang enums that appear in reflection, so that a [`ProgramReflection`] or a
+//! [`ShaderArtifact`] can be stored and loaded without `shader_slang`.
+//!
+//! [`ProgramReflection`]: crate::ProgramReflection
+//! [`ShaderArtifact`]: crate::ShaderArtifact
+
+/// Declares an enum mirroring the `shader_slang` enum of the same name, with a conversion from
+/// it. Variants missing here, e.g. ones added by newer Slang versions, convert to `$fallback`.
+macro_rules! slang_enum {
+    (
+        $(#[$meta:meta])*
+        pub enum $name:ident fallback $fallback:ident {
+            $($(#[$variant_meta:meta])* $variant:ident,)*
+        }
+    ) => {
+        $(#[$meta])*
+        #[cfg_attr(feature = "derive-serde", derive(serde::Serialize, serde::Deserialize))]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        pub enum $name {
+            $($(#[$variant_meta])* $variant,)*
+        }
+
+        impl From<shader_slang::$name> for $name {
+            #[allow(unreachable_patterns)]
+            fn from(value: shader_slang::$name) -> Self {
+                match value {
+                    $(shader_slang::$name::$variant => $name::$variant,)*
+                    _ => $name::$fallback,
+                }
+            }
+        }
+    };
+}
+
+/// Format of the code Slang generates.
+///
+/// Converts into [`slang::CompileTarget`](crate::slang::CompileTarget) for the raw Slang API:
+///
+/// ```
+/// use slang_reflector::{CompileTarget, slang};
+///
+/// let target: slang::CompileTarget = CompileTarget::Spirv.into();
+/// assert!(matches!(target, slang::CompileTarget::Spirv));
+/// let target_desc = slang::TargetDesc::default().format(CompileTarget::Wgsl.into());
+/// ```
+#[cfg_attr(feature = "derive-serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CompileTarget {
+    Glsl,
+    Hlsl,
+    Spirv,
+    SpirvAsm,
+    Dxbc,
+    DxbcAsm,
+    Dxil,
+    DxilAsm,
+    CSource,
+    CppSource,
+    CudaSource,
+    Ptx,
+    Metal,
+    MetalLib,
+    MetalLibAsm,
+    Wgsl,
+}
+
+impl From<CompileTarget> for shader_slang::CompileTarget {
+    fn from(target: CompileTarget) -> Self {
+        match target {
+            CompileTarget::Glsl => shader_slang::CompileTarget::Glsl,
+            CompileTarget::Hlsl => shader_slang::CompileTarget::Hlsl,
+            CompileTarget::Spirv => shader_slang::CompileTarget::Spirv,
+            CompileTarget::SpirvAsm => shader_slang::CompileTarget::SpirvAsm,
+            CompileTarget::Dxbc => shader_slang::CompileTarget::Dxbc,
+            CompileTarget::DxbcAsm => shader_slang::CompileTarget::DxbcAsm,
+            CompileTarget::Dxil => shader_slang::CompileTarget::Dxil,
+            CompileTarget::DxilAsm => shader_slang::CompileTarget::DxilAsm,
+            CompileTarget::CSource => shader_slang::CompileTarget::CSource,
+            CompileTarget::CppSource => shader_slang::CompileTarget::CppSource,
+            CompileTarget::CudaSource => shader_slang::CompileTarget::CudaSource,
+            CompileTarget::Ptx => shader_slang::CompileTarget::Ptx,
+            CompileTarget::Metal => shader_slang::CompileTarget::Metal,
+            CompileTarget::MetalLib => shader_slang::CompileTarget::MetalLib,
+            CompileTarget::MetalLibAsm => shader_slang::CompileTarget::MetalLibAsm,
+            CompileTarget::Wgsl => shader_slang::CompileTarget::Wgsl,
+        }
+    }
+}
+
+slang_enum! {
+    /// Pipeline stage of an entry point.
+    pub enum Stage fallback None {
+        None,
+        Vertex,
+        Hull,
+        Domain,
+        Geometry,
+        Fragment,
+        Compute,
+        RayGeneration,
+        Intersection,
+        AnyHit,
+        ClosestHit,
+        Miss,
+        Callable,
+        Mesh,
+        Amplification,
+    }
+}
+
+slang_enum! {
+    /// Kind of resource a parameter consumes, e.g. a descriptor slot or uniform bytes.
+    pub enum ParameterCategory fallback None {
+        None,
+        Mixed,
+        ConstantBuffer,
+        ShaderResource,
+        UnorderedAccess,
+        VaryingInput,
+        VaryingOutput,
+        SamplerState,
+        Uniform,
+        DescriptorTableSlot,
+        SpecializationConstant,
+        PushConstantBuffer,
+        RegisterSpace,
+        Generic,
+        RayPayload,
+        HitAttributes,
+        CallablePayload,
+        ShaderRecord,
+        SubElementRegisterSpace,
+    }
+}
+
+slang_enum! {
+    pub enum ScalarType fallback None {
+        None,
+        Void,
+        Bool,
+        Int32,
+        Uint32,
+        Int64,
+        Uint64,
+        Float16,
+        Float32,
+        Float64,
+        Int8,
+        Uint8,
+        Int16,
+        Uint16,
+        Intptr,
+        Uintptr,
+    }
+}
+
+slang_enum! {
+    pub enum ResourceAccess fallback None {
+        None,
+        Read,
+        ReadWrite,
+        RasterOrdered,
+        Append,
+        Consume,
+        Write,
+        Feedback,
+    }
+}
+
+slang_enum! {
+    /// Format declared on a storage texture, e.g. with `[format("rgba8")]`.
+    pub enum ImageFormat fallback Unknown {
+        Unknown,
+        Rgba32f,
+        Rgba16f,
+        Rg32f,
+        Rg16f,
+        R32f,
+        R16f,
+        Rgba16,
+        Rgb10A2,
+        Rgba8,
+        Rg16,
+        Rg8,
+        R16,
+        R8,
+        Rgba16Snorm,
+        Rgba8Snorm,
+        Rg16Snorm,
+        Rg8Snorm,
+        R16Snorm,
+        R8Snorm,
+        Rgba32i,
+        Rgba16i,
+        Rgba8i,
+        Rg32i,
+        Rg16i,
+        Rg8i,
+        R32i,
+        R16i,
+        R8i,
+        Rgba32ui,
+        Rgba16ui,
+        Rgb10A2ui,
+        Rgba8ui,
+        Rg32ui,
+        Rg16ui,
+        Rg8ui,
+        R32ui,
+        R16ui,
+        R8ui,
+        Bgra8,
+    }
+}
@@ -0,0 +1,245 @@
+//! An `ISlangFileSystem` serving the builder's virtual files, so that Slang resolves `import`
+//! and `#include` of in-memory files the same way it does files on disk.
+
+use std::collections::HashMap;
+use std::ffi::{CStr, c_char, c_void};
+use std::ptr::{NonNull, null_mut};
+use std::sync::atomic::{AtomicU32, Ordering};
+
+type SlangResult = i32;
+
+const SLANG_OK: SlangResult = 0;
+const SLANG_E_NO_INTERFACE: SlangResult = 0x8000_4002_u32 as i32;
+const SLANG_E_NOT_FOUND: SlangResult = 0x8200_0005_u32 as i32;
+
+/// Layout of `SlangUUID`.
+#[repr(C)]
+#[derive(PartialEq, Eq)]
+struct Uuid {
+    data1: u32,
+    data2: u16,
+    data3: u16,
+    data4: [u8; 8],
+}
+
+const ISLANG_UNKNOWN: Uuid = Uuid {
+    data1: 0x0000_0000,
+    data2: 0x0000,
+    data3: 0x0000,
+    data4: [0xc0, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x46],
+};
+const ISLANG_CASTABLE: Uuid = Uuid {
+    data1: 0x87ed_e0e1,
+    data2: 0x4852,
+    data3: 0x44b0,
+    data4: [0x8b, 0xf2, 0xcb, 0x31, 0x87, 0x4d, 0xe2, 0x39],
+};
+const ISLANG_FILE_SYSTEM: Uuid = Uuid {
+    data1: 0x003a_09fc,
+    data2: 0x3a4d,
+    data3: 0x4ba0,
+    data4: [0xad, 0x60, 0x1f, 0xd8, 0x63, 0xa9, 0x15, 0xab],
+};
+const ISLANG_BLOB: Uuid = Uuid {
+    data1: 0x8ba5_fb08,
+    data2: 0x5195,
+    data3: 0x40e2,
+    data4: [0xac, 0x58, 0x0d, 0x98, 0x9c, 0x3a, 0x01, 0x02],
+};
+
+/// A reference counted COM object: a vtable pointer followed by the Rust state.
+#[repr(C)]
+struct ComObject<V, T> {
+    vtable: *const V,
+    ref_count: AtomicU32,
+    interfaces: &'static [Uuid],
+    value: T,
+}
+
+impl<V, T> ComObject<V, T> {
+    /// Allocates the object with one reference, owned by the caller.
+    fn new(vtable: *const V, interfaces: &'static [Uuid], value: T) -> NonNull<Self> {
+        NonNull::from(Box::leak(Box::new(ComObject {
+            vtable,
+            ref_count: AtomicU32::new(1),
+            interfaces,
+            value,
+        })))
+    }
+}
+
+unsafe extern "C" fn query_interface<V, T>(
+    this: *mut c_void,
+    uuid: *const Uuid,
+    out_object: *mut *mut c_void,
+) -> SlangResult {
+    // SAFETY: Slang only calls the vtable with the object it belongs to and valid pointers.
+    unsafe {
+        if (*this.cast::<ComObject<V, T>>())
+            .interfaces
+            .contains(&*uuid)
+        {
+            add_ref::<V, T>(this);
+            *out_object = this;
+            SLANG_OK
+        } else {
+            *out_object = null_mut();
+            SLANG_E_NO_INTERFACE
+        }
+    }
+}
+
+unsafe extern "C" fn add_ref<V, T>(this: *mut c_void) -> u32 {
+    // SAFETY: `this` is a live object.
+    let object = unsafe { &*this.cast::<ComObject<V, T>>() };
+    object.ref_count.fetch_add(1, Ordering::Relaxed) + 1
+}
+
+unsafe extern "C" fn release<V, T>(this: *mut c_void) -> u32 {
+    let object = this.cast::<ComObject<V, T>>();
+    // SAFETY: `this` is a live object and the caller gives up one reference to it.
+    let count = unsafe { (*object).ref_count.fetch_sub(1, Ordering::AcqRel) } - 1;
+    if count == 0 {
+        // SAFETY: that was the last reference, and the object was allocated by `ComObject::new`.
+        drop(unsafe { Box::from_raw(object) });
+    }
+    count
+}
+
+unsafe extern "C" fn cast_as<V, T>(this: *mut c_void, uuid: *const Uuid) -> *mut c_void {
+    // SAFETY: as in `query_interface`. `castAs` doesn't add a reference.
+    let interfaces = unsafe { (*this.cast::<ComObject<V, T>>()).interfaces };
+    if interfaces.contains(unsafe { &*uuid }) {
+        this
+    } else {
+        null_mut()
+    }
+}
+
+/// Layout of the `ISlangFileSystem` vtable.
+#[repr(C)]
+struct FileSystemVtable {
+    query_interface: unsafe extern "C" fn(*mut c_void, *const Uuid, *mut *mut c_void) -> i32,
+    add_ref: unsafe extern "C" fn(*mut c_void) -> u32,
+    release: unsafe extern "C" fn(*mut c_void) -> u32,
+    cast_as: unsafe extern "C" fn(*mut c_void, *const Uuid) -> *mut c_void,
+    load_file: unsafe extern "C" fn(*mut c_void, *const c_char, *mut *mut c_void) -> i32,
+}
+
+/// Layout of the `ISlangBlob` vtable.
+#[repr(C)]
+struct BlobVtable {
+    query_interface: unsafe extern "C" fn(*mut c_void, *const Uuid, *mut *mut c_void) -> i32,
+    add_ref: unsafe extern "C" fn(*mut c_void) -> u32,
+    release: unsafe extern "C" fn(*mut c_void) -> u32,
+    get_buffer_pointer: unsafe extern "C" fn(*mut c_void) -> *const c_void,
+    get_buffer_size: unsafe extern "C" fn(*mut c_void) -> usize,
+}
+
+type FileSystemObject = ComObject<FileSystemVtable, HashMap<String, String>>;
+type BlobObject = ComObject<BlobVtable, Vec<u8>>;
+
+static FILE_SYSTEM_VTABLE: FileSystemVtable = FileSystemVtable {
+    query_interface: query_interface::<FileSystemVtable, HashMap<String, String>>,
+    add_ref: add_ref::<FileSystemVtable, HashMap<String, String>>,
+    release: release::<FileSystemVtable, HashMap<String, String>>,
+    cast_as: cast_as::<FileSystemVtable, HashMap<String, String>>,
+    load_file,
+};
+
+static BLOB_VTABLE: BlobVtable = BlobVtable {
+    query_interface: query_interface::<BlobVtable, Vec<u8>>,
+    add_ref: add_ref::<BlobVtable, Vec<u8>>,
+    release: release::<BlobVtable, Vec<u8>>,
+    get_buffer_pointer,
+    get_buffer_size,
+};
+
+/// Reads a virtual file, or the file on disk when there is no virtual file at `path`.
+unsafe extern "C" fn load_file(
+    this: *mut c_void,
+    path: *const c_char,
+    out_blob: *mut *mut c_void,
+) -> SlangResult {
+    // SAFETY: `this` is a live file system and `path` a NUL-terminated string.
+    let (files, path) = unsafe {
+        (
+            &(*this.cast::<FileSystemObject>()).value,
+            CStr::from_ptr(path),
+        )
+    };
+    let Ok(path) = path.to_str() else {
+        return SLANG_E_NOT_FOUND;
+    };
+    let contents = match files.get(&normalized_virtual_path(path)) {
+        Some(contents) => contents.clone().into_bytes(),
+        None => match std::fs::read(path) {
+            Ok(contents) => contents,
+            Err(_) => return SLANG_E_NOT_FOUND,
+        },
+    };
+    let blob = BlobObject::new(&BLOB_VTABLE, &[ISLANG_UNKNOWN, ISLANG_BLOB], contents);
+    // SAFETY: `out_blob` is valid for writes; the caller takes over the blob's reference.
+    unsafe { *out_blob = blob.as_ptr().cast() };
+    SLANG_OK
+}
+
+unsafe extern "C" fn get_buffer_pointer(this: *mut c_void) -> *const c_void {
+    // SAFETY: `this` is a live blob.
+    unsafe { (*this.cast::<BlobObject>()).value.as_ptr().cast() }
+}
+
+unsafe extern "C" fn get_buffer_size(this: *mut c_void) -> usize {
+    // SAFETY: `this` is a live blob.
+    unsafe { (*this.cast::<BlobObject>()).value.len() }
+}
+
+/// `path` with `/` separators, without `.` components and with `..` applied, so
+/// `./utils/../math.slang` matches the virtual file `math.slang`.
+fn normalized_virtual_path(path: &str) -> String {
+    let root = if path.starts_with(['/', '\\']) {
+        "/"
+    } else {
+        ""
+    };
+    let mut components = Vec::new();
+    for component in path.split(['/', '\\']) {
+        match component {
+            "" | "." => {}
+            ".." => {
+                components.pop();
+            }
+            component => components.push(component),
+        }
+    }
+    format!("{root}{}", components.join("/"))
+}
+
+/// An owned reference to an `ISlangFileSystem` holding the virtual files.
+pub(crate) struct VirtualFileSystem(NonNull<FileSystemObject>);
+
+impl VirtualFileSystem {
+    pub(crate) fn new(files: &[(String, String)]) -> Self {
+        let files = files
+            .iter()
+            .map(|(path, contents)| (normalized_virtual_path(path), contents.clone()))
+            .collect();
+        VirtualFileSystem(FileSystemObject::new(
+            &FILE_SYSTEM_VTABLE,
+            &[ISLANG_UNKNOWN, ISLANG_CASTABLE, ISLANG_FILE_SYSTEM],
+            files,
+        ))
+    }
+
+    /// The `ISlangFileSystem*` to put in a `SessionDesc`, which adds its own reference.
+    pub(crate) fn as_raw(&self) -> *mut c_void {
+        self.0.as_ptr().cast()
+    }
+}
+
+impl Drop for VirtualFileSystem {
+    fn drop(&mut self) {
+        // SAFETY: this gives up the reference `new` handed out.
+        unsafe { release::<FileSystemVtable, HashMap<String, String>>(self.as_raw()) };
+    }
+}
@@ -1,11 +1,38 @@
 use std::collections::HashMap;
+use std::fmt;
 
+mod artifact;
+#[cfg(feature = "ash")]
+pub mod ash;
+#[cfg(any(feature = "wgpu", feature = "ash"))]
+mod bindings;
+#[cfg(feature = "build")]
+pub mod build;
+mod codegen;
+mod diagnostics;
+mod enums;
+mod file_system;
+pub mod playground;
+mod reflector;
+#[cfg(feature = "wgpu")]
+pub mod wgpu;
+
+pub use artifact::{EntryPointCode, ShaderArtifact, TargetArtifact};
+pub use codegen::{CodegenError, RustCodegen};
+pub use diagnostics::{Diagnostic, Severity};
+pub use enums::{CompileTarget, ImageFormat, ParameterCategory, ResourceAccess, ScalarType, Stage};
+pub use reflector::{
+    CompiledProgram, Reflector, ReflectorBuilder, ReflectorError, TargetReflection,
+};
+
+/// The `shader_slang` crate, for the raw Slang API. Its enums, e.g. `slang::CompileTarget`, are
+/// distinct from the ones of the same name in this crate, which convert into them with `.into()`.
+pub use shader_slang as slang;
 pub use shader_slang::{
-    Blob, CompileTarget, CompilerOptions, ComponentType, Error, GlobalSession,
-    ImageFormat, Module, OptimizationLevel, ResourceAccess, Result, ScalarType, Session,
-    SessionDesc, Stage, TargetDesc, Downcast,
+    Blob, CompilerOptions, ComponentType, Downcast, Error, Module, OptimizationLevel,
+    ResourceShape, Result, Session, TypeKind,
 };
-use shader_slang::{reflection::UserAttribute, ParameterCategory, ResourceShape, TypeKind};
+use shader_slang::{MatrixLayoutMode, reflection::UserAttribute};
 
 #[cfg_attr(feature = "derive-serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Clone)]
@@ -31,6 +58,13 @@ pub enum TextureType {
     Cube,
 }
 
+#[cfg_attr(feature = "derive-serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MatrixLayout {
+    RowMajor,
+    ColumnMajor,
+}
+
 #[cfg_attr(feature = "derive-serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Clone)]
 pub struct VariableReflection {
@@ -45,11 +79,61 @@ pub enum BoundParameter {
     Uniform {
         uniform_offset: usize,
         resource_result: VariableReflectionType,
+        layout: TypeLayoutReflection,
     },
     Resource {
         resource: BoundResource,
         binding_index: u32,
+        /// Register space on D3D targets, descriptor set on Vulkan and `@group` on WGSL.
+        binding_space: u32,
+        /// The kind of binding slot the resource occupies, e.g.
+        /// [`ParameterCategory::DescriptorTableSlot`] on Vulkan or
+        /// [`ParameterCategory::ShaderResource`] for an HLSL `t` register.
+        category: ParameterCategory,
     },
+    /// A `[[vk::push_constant]]` block, written with push constants rather than bound.
+    PushConstants(PushConstantBlock),
+    /// A `[SpecializationConstant]` or `[vk::constant_id(id)]` constant, fixed when the pipeline
+    /// is created.
+    SpecializationConstant(SpecializationConstantReflection),
+}
+
+/// Contents of a push-constant block.
+#[cfg_attr(feature = "derive-serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+pub struct PushConstantBlock {
+    /// Size in bytes of the block's uniform data.
+    pub size: usize,
+    /// The block's struct, for generating a matching Rust type.
+    pub element_type: VariableReflectionType,
+    /// Fields of the block; uniform offsets are relative to the start of the block.
+    pub variables: Vec<VariableReflection>,
+}
+
+impl PushConstantBlock {
+    /// Byte range of the block within the push-constant range. Slang lays out every block from
+    /// offset 0, so blocks of different entry points overlap.
+    pub fn range(&self) -> std::ops::Range<u32> {
+        0..self.size as u32
+    }
+}
+
+#[cfg_attr(feature = "derive-serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+pub struct SpecializationConstantReflection {
+    /// The `constant_id` of the constant in SPIR-V.
+    pub constant_id: u32,
+    pub scalar_type: ScalarType,
+    /// The value used when the pipeline doesn't specialize the constant.
+    pub default_value: Option<SpecializationConstantValue>,
+}
+
+#[cfg_attr(feature = "derive-serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SpecializationConstantValue {
+    Bool(bool),
+    Int(i64),
+    Float(f64),
 }
 
 #[cfg_attr(feature = "derive-serde", derive(serde::Serialize, serde::Deserialize))]
@@ -57,55 +141,179 @@ pub enum BoundParameter {
 pub enum BoundResource {
     StructuredBuffer {
         resource_result: VariableReflectionType,
+        /// Layout of a single element; `stride` is the buffer's element stride.
+        element_layout: TypeLayoutReflection,
+        resource_access: ResourceAccess,
+    },
+    /// An `AppendStructuredBuffer` or `ConsumeStructuredBuffer`.
+    AppendConsumeBuffer {
+        resource_result: VariableReflectionType,
+        element_layout: TypeLayoutReflection,
+        /// Either [`ResourceAccess::Append`] or [`ResourceAccess::Consume`].
+        resource_access: ResourceAccess,
+        /// The hidden buffer holding the element count, if the target needs a separate one.
+        counter: Option<CounterBinding>,
+    },
+    /// A `ByteAddressBuffer` or `RWByteAddressBuffer`.
+    RawBuffer {
         resource_access: ResourceAccess,
     },
-    Sampler,
+    Sampler {
+        /// Whether this is a `SamplerComparisonState`, used for depth comparisons.
+        is_comparison: bool,
+    },
     Texture {
         tex_type: TextureType,
         resource_result: VariableReflectionType,
         format: ImageFormat,
         resource_access: ResourceAccess,
+        is_array: bool,
+        is_multisampled: bool,
+        /// Whether the texture is combined with a comparison (shadow) sampler.
+        is_shadow: bool,
+    },
+    /// A texture and sampler declared as one object, e.g. `Sampler2D<float4>`.
+    CombinedTextureSampler {
+        tex_type: TextureType,
+        resource_result: VariableReflectionType,
+        format: ImageFormat,
+        is_array: bool,
+        is_multisampled: bool,
+        is_shadow: bool,
+        /// Binding of the sampler; equal to the texture's on targets with combined samplers.
+        sampler_binding_index: u32,
+        sampler_binding_space: u32,
+    },
+    /// A `FeedbackTexture2D` or `FeedbackTexture2DArray` for sampler feedback.
+    FeedbackTexture {
+        tex_type: TextureType,
+        is_array: bool,
     },
+    /// A typed texel buffer, e.g. `Buffer<float4>` or `RWBuffer<uint>`.
+    TexelBuffer {
+        resource_result: VariableReflectionType,
+        format: ImageFormat,
+        resource_access: ResourceAccess,
+    },
+    ConstantBuffer(UniformBlock),
+    ParameterBlock(UniformBlock),
+    TextureBuffer(UniformBlock),
+    /// An array of resources occupying `count` consecutive descriptors, e.g.
+    /// `Texture2D textures[16]` or a bindless `Texture2D textures[]`.
+    Array {
+        element: Box<BoundResource>,
+        count: DescriptorCount,
+    },
+    /// A `__DynamicResource` whose concrete type is only known at runtime (descriptor heap
+    /// style bindless access). Reflection gives it a binding, recorded on the enclosing
+    /// [`BoundParameter::Resource`], but no resource type, shape or access.
+    DynamicResource,
+}
+
+#[cfg_attr(feature = "derive-serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DescriptorCount {
+    Fixed(usize),
+    /// A runtime-sized array, e.g. `Texture2D textures[]`.
+    Unbounded,
+}
+
+/// Binding of the counter buffer that backs an append or consume buffer.
+#[cfg_attr(feature = "derive-serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CounterBinding {
+    pub binding_index: u32,
+    pub binding_space: u32,
+}
+
+/// Contents of a `ConstantBuffer<T>`, `ParameterBlock<T>` or `TextureBuffer<T>`.
+#[cfg_attr(feature = "derive-serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+pub struct UniformBlock {
+    /// Register space (HLSL) or descriptor set / bind group (Vulkan, WGSL) of the block.
+    pub space: u32,
+    /// Size in bytes of the uniform data of `T`.
+    pub uniform_size: usize,
+    /// `T` itself. Fields without uniform data, e.g. textures in a parameter block, are left
+    /// out, so the struct describes the contents of the block's uniform buffer.
+    pub element_type: VariableReflectionType,
+    /// Fields of `T`; uniform offsets and binding indices are relative to the block. Binding
+    /// indices start after the block's uniform buffer when it has one.
+    pub variables: Vec<VariableReflection>,
+}
+
+/// Size, alignment and stride in bytes of a type, as laid out by Slang for the target's layout
+/// rules (std140, std430, scalar, D3D constant buffer, ...).
+#[cfg_attr(feature = "derive-serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TypeLayoutReflection {
+    pub size: usize,
+    pub alignment: usize,
+    /// Distance between consecutive elements when the type is stored in an array.
+    pub stride: usize,
+}
+
+impl TypeLayoutReflection {
+    fn from_slang_layout(type_layout: &shader_slang::reflection::TypeLayout) -> Self {
+        TypeLayoutReflection {
+            size: type_layout.size(shader_slang::ParameterCategory::Uniform),
+            alignment: type_layout
+                .alignment(shader_slang::ParameterCategory::Uniform)
+                .max(0) as usize,
+            stride: type_layout.stride(shader_slang::ParameterCategory::Uniform),
+        }
+    }
+}
+
+#[cfg_attr(feature = "derive-serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+pub struct StructFieldReflection {
+    pub name: String,
+    /// Byte offset of the field from the start of the struct.
+    pub offset: usize,
+    pub layout: TypeLayoutReflection,
+    pub reflection_type: VariableReflectionType,
 }
 
 #[cfg_attr(feature = "derive-serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Clone)]
 pub enum VariableReflectionType {
     Struct {
-        name: String, 
-        props: Vec<(String, VariableReflectionType)>,
+        name: String,
+        props: Vec<StructFieldReflection>,
         user_attributes: Vec<UserAttributeReflection>,
+        layout: TypeLayoutReflection,
     },
     Scalar(ScalarType),
     Vector(ScalarType, usize),
-    Array(Box<VariableReflectionType>, usize),
-}
-
-fn get_scalar_size(scalar_type: &ScalarType) -> u32 {
-    match scalar_type {
-        ScalarType::Int8 | ScalarType::Uint8 => 1,
-        ScalarType::Int16 | ScalarType::Uint16 | ScalarType::Float16 => 2,
-        ScalarType::Int32 | ScalarType::Uint32 | ScalarType::Float32 => 4,
-        ScalarType::Int64 | ScalarType::Uint64 | ScalarType::Float64 => 8,
-        _ => panic!("Unrecognized scalar type"),
-    }
+    /// A `rows` x `columns` matrix, stored as `rows` row vectors when `layout` is
+    /// [`MatrixLayout::RowMajor`] and as `columns` column vectors otherwise, `stride` bytes apart.
+    Matrix {
+        scalar: ScalarType,
+        rows: usize,
+        columns: usize,
+        layout: MatrixLayout,
+        stride: usize,
+    },
+    Array {
+        element: Box<VariableReflectionType>,
+        /// Zero for unbounded arrays.
+        count: usize,
+        /// Layout of a single element; `stride` is the array's element stride.
+        element_layout: TypeLayoutReflection,
+    },
 }
 
-impl VariableReflectionType {
-    pub fn get_size(&self) -> u32 {
-        match self {
-            VariableReflectionType::Scalar(scalar_type) => get_scalar_size(scalar_type),
-            VariableReflectionType::Vector(scalar_type, count) => {
-                let count = count.next_power_of_two() as u32;
-                count * get_scalar_size(scalar_type)
-            }
-            VariableReflectionType::Struct{ props, .. } => props
-                .iter()
-                .map(|(_, field_data)| field_data.get_size())
-                .fold(0, |a, f| (a + f).div_ceil(f) * f),
-            VariableReflectionType::Array(ty, count) => ty.get_size() * *count as u32,
-        }
-    }
+/// A varying entry point input or output, such as a vertex attribute or `SV_DispatchThreadID`.
+#[cfg_attr(feature = "derive-serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+pub struct VaryingReflection {
+    /// Parameter name, with struct fields joined by `.` (e.g. `input.normal`). The return value
+    /// is named `result`.
+    pub name: String,
+    pub semantic_name: Option<String>,
+    pub semantic_index: usize,
+    pub reflection_type: VariableReflectionType,
 }
 
 #[cfg_attr(feature = "derive-serde", derive(serde::Serialize, serde::Deserialize))]
@@ -113,70 +321,463 @@ impl VariableReflectionType {
 pub struct EntrypointReflection {
     pub name: String,
     pub user_attributes: Vec<UserAttributeReflection>,
+    pub stage: Stage,
+    /// The `[numthreads(x, y, z)]` size of compute, mesh and amplification shaders.
+    pub thread_group_size: Option<[u64; 3]>,
+    pub inputs: Vec<VaryingReflection>,
+    pub outputs: Vec<VaryingReflection>,
+    /// Paths of varying parameters whose type can't be reflected, e.g. a hull shader's
+    /// `InputPatch<T, N>`; they are left out of `inputs` and `outputs`.
+    pub skipped_varyings: Vec<String>,
+    /// `uniform` entry point parameters, laid out relative to the entry point.
+    pub uniforms: Vec<VariableReflection>,
+    /// The buffer Slang wraps around the `uniform` parameters that hold ordinary data, e.g.
+    /// `uniform float4 tint`, if there are any.
+    pub uniform_buffer: Option<EntryPointUniformBuffer>,
+}
+
+/// Where the ordinary data of an entry point's `uniform` parameters lives.
+#[cfg_attr(feature = "derive-serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EntryPointUniformBuffer {
+    /// A constant buffer, as on WGSL and D3D.
+    ConstantBuffer {
+        binding_index: u32,
+        binding_space: u32,
+        /// Size in bytes of the uniform data.
+        size: usize,
+    },
+    /// A push-constant block, as on Vulkan.
+    PushConstants { size: usize },
 }
 
 #[cfg_attr(feature = "derive-serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Clone)]
 pub struct ProgramReflection {
     pub variables: Vec<VariableReflection>,
+    /// The constant buffer holding the global [`BoundParameter::Uniform`] variables, if there
+    /// are any.
+    pub global_uniform_buffer: Option<GlobalUniformBuffer>,
     pub entry_points: Vec<EntrypointReflection>,
     pub hashed_strings: HashMap<u32, String>,
 }
 
+/// Binding of the constant buffer Slang wraps around global `uniform` variables.
+#[cfg_attr(feature = "derive-serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GlobalUniformBuffer {
+    pub binding_index: u32,
+    pub binding_space: u32,
+    /// Size in bytes of the uniform data.
+    pub size: usize,
+}
+
+/// Why reflection of a particular variable failed.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ReflectErrorReason {
+    /// The Slang type kind has no corresponding [`VariableReflectionType`].
+    UnsupportedType,
+    /// The resource shape has no corresponding [`BoundResource`].
+    UnsupportedResourceShape,
+    /// The type cannot appear as a global shader parameter.
+    UnsupportedTopLevelType,
+    /// Slang returned no data for an accessor that was expected to succeed.
+    Missing(&'static str),
+}
+
+/// Error produced by [`ProgramLayoutReflector::try_reflect`].
+#[derive(Debug, Clone)]
+pub struct ReflectError {
+    /// Dotted path to the offending variable, e.g. `material.albedo` or `lights[]`.
+    pub path: String,
+    pub type_kind: Option<TypeKind>,
+    pub resource_shape: Option<ResourceShape>,
+    pub reason: ReflectErrorReason,
+}
+
+impl ReflectError {
+    fn new(path: &str, reason: ReflectErrorReason) -> Self {
+        ReflectError {
+            path: path.to_string(),
+            type_kind: None,
+            resource_shape: None,
+            reason,
+        }
+    }
+
+    fn missing(path: &str, what: &'static str) -> Self {
+        Self::new(path, ReflectErrorReason::Missing(what))
+    }
+
+    fn with_type_kind(mut self, type_kind: TypeKind) -> Self {
+        self.type_kind = Some(type_kind);
+        self
+    }
+
+    fn with_resource_shape(mut self, resource_shape: ResourceShape) -> Self {
+        self.resource_shape = Some(resource_shape);
+        self
+    }
+}
+
+impl fmt::Display for ReflectError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let path = if self.path.is_empty() {
+            "<global>"
+        } else {
+            self.path.as_str()
+        };
+        match &self.reason {
+            ReflectErrorReason::UnsupportedType => write!(f, "`{path}`: unsupported type")?,
+            ReflectErrorReason::UnsupportedResourceShape => {
+                write!(f, "`{path}`: unsupported resource shape")?
+            }
+            ReflectErrorReason::UnsupportedTopLevelType => write!(
+                f,
+                "`{path}`: type is not valid as a top level shader parameter"
+            )?,
+            ReflectErrorReason::Missing(what) => {
+                write!(f, "`{path}`: slang reflection returned no {what}")?
+            }
+        }
+        if let Some(type_kind) = self.type_kind {
+            write!(f, " (type kind {type_kind:?})")?;
+        }
+        if let Some(resource_shape) = self.resource_shape {
+            write!(f, " (resource shape {resource_shape:?})")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ReflectError {}
+
+pub type ReflectResult<T> = std::result::Result<T, ReflectError>;
+
 pub trait ProgramLayoutReflector {
-    fn reflect(&self) -> ProgramReflection;
+    fn try_reflect(&self) -> ReflectResult<ProgramReflection>;
+
+    /// Like [`try_reflect`](ProgramLayoutReflector::try_reflect), but panics on unsupported shaders.
+    fn reflect(&self) -> ProgramReflection {
+        self.try_reflect().unwrap_or_else(|e| panic!("{e}"))
+    }
 }
 
 impl ProgramLayoutReflector for shader_slang::reflection::Shader {
-    fn reflect(&self) -> ProgramReflection {
-        let global_layout = self.global_params_type_layout().unwrap();
+    fn try_reflect(&self) -> ReflectResult<ProgramReflection> {
+        let global_layout = self
+            .global_params_type_layout()
+            .ok_or_else(|| ReflectError::missing("", "global parameter layout"))?;
+        let mut global_uniform_buffer = None;
         let var_reflection = if matches!(global_layout.kind(), TypeKind::ConstantBuffer) {
-            global_layout.element_type_layout().unwrap()
+            let element_layout = global_layout.element_type_layout().ok_or_else(|| {
+                ReflectError::missing("", "global constant buffer element layout")
+            })?;
+            let global_var_layout = self
+                .global_params_var_layout()
+                .ok_or_else(|| ReflectError::missing("", "global parameter variable layout"))?;
+            global_uniform_buffer = Some(GlobalUniformBuffer {
+                binding_index: global_var_layout.binding_index(),
+                binding_space: global_var_layout.binding_space(),
+                size: element_layout.size(shader_slang::ParameterCategory::Uniform),
+            });
+            element_layout
         } else {
             global_layout
         };
 
-        let mut variables = Vec::new();
-
-        for parameter in var_reflection.fields() {
-            let reflection_type =
-                bound_resource_from_slang_type(parameter.type_layout().unwrap().ty().unwrap(), parameter);
-            let user_attributes =
-                parameter_user_attributes(parameter.variable().unwrap().user_attributes());
-            variables.push(VariableReflection {
-                name: parameter.variable().unwrap().name().unwrap().to_string(),
-                reflection_type,
-                user_attributes,
-            })
-        }
+        let variables = reflect_variables(var_reflection, "")?;
 
         let mut entry_points = Vec::new();
 
         for entry_point in self.entry_points() {
+            let name = entry_point
+                .name()
+                .ok_or_else(|| ReflectError::missing("", "entry point name"))?
+                .to_string();
+            let function = entry_point
+                .function()
+                .ok_or_else(|| ReflectError::missing(&name, "entry point function"))?;
+            let stage = Stage::from(entry_point.stage());
+            let thread_group_size =
+                matches!(stage, Stage::Compute | Stage::Mesh | Stage::Amplification)
+                    .then(|| entry_point.compute_thread_group_size());
+
+            let mut inputs = Vec::new();
+            let mut outputs = Vec::new();
+            let mut skipped_varyings = Vec::new();
+            let mut uniforms = Vec::new();
+            for parameter in entry_point.parameters() {
+                let parameter_name = parameter
+                    .variable()
+                    .and_then(|v| v.name())
+                    .ok_or_else(|| ReflectError::missing(&name, "parameter name"))?;
+                // `inout` parameters, e.g. a geometry shader's `inout TriangleStream<T>`, are
+                // both varying input and output.
+                let categories = match parameter.category() {
+                    Some(shader_slang::ParameterCategory::Mixed) => parameter
+                        .type_layout()
+                        .map(|layout| {
+                            (0..layout.category_count())
+                                .map(|i| layout.category_by_index(i))
+                                .collect()
+                        })
+                        .unwrap_or_default(),
+                    Some(category) => vec![category],
+                    None => Vec::new(),
+                };
+                let is_input = categories
+                    .iter()
+                    .any(|c| matches!(c, shader_slang::ParameterCategory::VaryingInput));
+                let is_output = categories
+                    .iter()
+                    .any(|c| matches!(c, shader_slang::ParameterCategory::VaryingOutput));
+                let is_system_value = categories
+                    .iter()
+                    .all(|c| matches!(c, shader_slang::ParameterCategory::None));
+                if is_input || is_system_value {
+                    let varyings = Varyings {
+                        list: &mut inputs,
+                        skipped: &mut skipped_varyings,
+                    };
+                    reflect_varyings(parameter, parameter_name, &name, varyings)?;
+                }
+                if is_output {
+                    let varyings = Varyings {
+                        list: &mut outputs,
+                        skipped: &mut skipped_varyings,
+                    };
+                    reflect_varyings(parameter, parameter_name, &name, varyings)?;
+                }
+                if !is_input && !is_output && !is_system_value {
+                    uniforms.push(reflect_variable(parameter, &name)?);
+                }
+            }
+            let returns_void = function.return_type().is_none_or(|ty| {
+                matches!(ty.kind(), TypeKind::Scalar)
+                    && matches!(ty.scalar_type(), shader_slang::ScalarType::Void)
+            });
+            if !returns_void {
+                let result = entry_point
+                    .result_var_layout()
+                    .ok_or_else(|| ReflectError::missing(&name, "result layout"))?;
+                let varyings = Varyings {
+                    list: &mut outputs,
+                    skipped: &mut skipped_varyings,
+                };
+                reflect_varyings(result, "result", &name, varyings)?;
+            }
+
+            let uniform_buffer = entry_point_uniform_buffer(entry_point, &name)?;
+
             entry_points.push(EntrypointReflection {
-                name: entry_point.name().unwrap().to_string(),
-                user_attributes: parameter_user_attributes(
-                    entry_point.function().unwrap().user_attributes(),
-                ),
+                user_attributes: parameter_user_attributes(function.user_attributes(), &name)?,
+                name,
+                stage,
+                thread_group_size,
+                inputs,
+                outputs,
+                skipped_varyings,
+                uniforms,
+                uniform_buffer,
             })
         }
 
         let hashed_strings = (0..self.hashed_string_count())
-            .map(|i| self.hashed_string(i).unwrap().to_string())
-            .map(|s| (shader_slang::reflection::compute_string_hash(s.as_str()), s))
-            .collect();
+            .map(|i| {
+                self.hashed_string(i)
+                    .map(str::to_string)
+                    .ok_or_else(|| ReflectError::missing("", "hashed string"))
+            })
+            .map(|s| s.map(|s| (shader_slang::reflection::compute_string_hash(s.as_str()), s)))
+            .collect::<ReflectResult<_>>()?;
 
-        ProgramReflection {
+        Ok(ProgramReflection {
             variables,
+            global_uniform_buffer,
             entry_points,
             hashed_strings,
+        })
+    }
+}
+
+/// Reflects every field of `type_layout` as a shader parameter, e.g. the globals of a program or
+/// the contents of a `ParameterBlock`.
+fn reflect_variables(
+    type_layout: &shader_slang::reflection::TypeLayout,
+    path: &str,
+) -> ReflectResult<Vec<VariableReflection>> {
+    type_layout
+        .fields()
+        .map(|parameter| reflect_variable(parameter, path))
+        .collect()
+}
+
+fn reflect_variable(
+    parameter: &shader_slang::reflection::VariableLayout,
+    path: &str,
+) -> ReflectResult<VariableReflection> {
+    let variable = parameter
+        .variable()
+        .ok_or_else(|| ReflectError::missing(path, "parameter variable"))?;
+    let name = variable
+        .name()
+        .ok_or_else(|| ReflectError::missing(path, "parameter name"))?
+        .to_string();
+    let parameter_path = join_path(path, &name);
+    let slang_type = parameter
+        .type_layout()
+        .and_then(|l| l.ty())
+        .ok_or_else(|| ReflectError::missing(&parameter_path, "type"))?;
+    let reflection_type = bound_resource_from_slang_type(slang_type, parameter, &parameter_path)?;
+    let user_attributes = parameter_user_attributes(variable.user_attributes(), &parameter_path)?;
+    Ok(VariableReflection {
+        name,
+        reflection_type,
+        user_attributes,
+    })
+}
+
+fn entry_point_uniform_buffer(
+    entry_point: &shader_slang::reflection::EntryPoint,
+    path: &str,
+) -> ReflectResult<Option<EntryPointUniformBuffer>> {
+    let var_layout = entry_point
+        .var_layout()
+        .ok_or_else(|| ReflectError::missing(path, "entry point variable layout"))?;
+    let type_layout = var_layout
+        .type_layout()
+        .ok_or_else(|| ReflectError::missing(path, "entry point type layout"))?;
+    // Without ordinary data the parameters are laid out as a plain struct.
+    if !matches!(type_layout.kind(), TypeKind::ConstantBuffer) {
+        return Ok(None);
+    }
+    let element_layout = type_layout
+        .element_type_layout()
+        .ok_or_else(|| ReflectError::missing(path, "entry point uniform buffer element layout"))?;
+    let size = element_layout.size(shader_slang::ParameterCategory::Uniform);
+    if size == 0 {
+        return Ok(None);
+    }
+    Ok(Some(
+        if type_layout.size(shader_slang::ParameterCategory::PushConstantBuffer) > 0 {
+            EntryPointUniformBuffer::PushConstants { size }
+        } else {
+            EntryPointUniformBuffer::ConstantBuffer {
+                binding_index: var_layout.binding_index(),
+                binding_space: var_layout.binding_space(),
+                size,
+            }
+        },
+    ))
+}
+
+/// Where [`reflect_varyings`] puts what it finds.
+struct Varyings<'a> {
+    list: &'a mut Vec<VaryingReflection>,
+    /// Paths of the varyings whose type has no [`VariableReflectionType`].
+    skipped: &'a mut Vec<String>,
+}
+
+/// Flattens a varying parameter into its leaves, descending into structs whose fields carry
+/// their own semantics.
+fn reflect_varyings(
+    slang_layout: &shader_slang::reflection::VariableLayout,
+    name: &str,
+    path: &str,
+    varyings: Varyings,
+) -> ReflectResult<()> {
+    let type_layout = slang_layout
+        .type_layout()
+        .ok_or_else(|| ReflectError::missing(&join_path(path, name), "type layout"))?;
+    reflect_varying_type(
+        type_layout,
+        slang_layout.semantic_name(),
+        slang_layout.semantic_index(),
+        name,
+        path,
+        varyings,
+    )
+}
+
+fn reflect_varying_type(
+    type_layout: &shader_slang::reflection::TypeLayout,
+    semantic_name: Option<&str>,
+    semantic_index: usize,
+    name: &str,
+    path: &str,
+    varyings: Varyings,
+) -> ReflectResult<()> {
+    let varying_path = join_path(path, name);
+    match type_layout.kind() {
+        // Builtin structs such as `InputPatch<T, N>` have no fields, and nothing to reflect.
+        TypeKind::Struct if semantic_name.is_none() && type_layout.fields().next().is_none() => {
+            varyings.skipped.push(varying_path);
+            return Ok(());
+        }
+        TypeKind::Struct if semantic_name.is_none() => {
+            for field in type_layout.fields() {
+                let field_name = field
+                    .variable()
+                    .and_then(|v| v.name())
+                    .ok_or_else(|| ReflectError::missing(&varying_path, "field name"))?;
+                let field_varyings = Varyings {
+                    list: &mut *varyings.list,
+                    skipped: &mut *varyings.skipped,
+                };
+                reflect_varyings(field, &join_path(name, field_name), path, field_varyings)?;
+            }
+            return Ok(());
+        }
+        // A geometry shader's `TriangleStream<T>` or a mesh shader's output array carries the
+        // varyings of `T`.
+        TypeKind::OutputStream | TypeKind::MeshOutput => {
+            if let Some(element_layout) = type_layout.element_type_layout() {
+                return reflect_varying_type(
+                    element_layout,
+                    semantic_name,
+                    semantic_index,
+                    name,
+                    path,
+                    varyings,
+                );
+            }
         }
+        _ => {}
+    }
+    let slang_type = type_layout
+        .ty()
+        .ok_or_else(|| ReflectError::missing(&varying_path, "type"))?;
+    match reflection_type_from_slang_type(slang_type, Some(type_layout), &varying_path) {
+        Ok(reflection_type) => varyings.list.push(VaryingReflection {
+            name: name.to_string(),
+            semantic_name: semantic_name.map(str::to_string),
+            semantic_index,
+            reflection_type,
+        }),
+        // Patches and other types without a `VariableReflectionType` shouldn't keep the rest
+        // of the program from being reflected.
+        Err(ReflectError {
+            reason: ReflectErrorReason::UnsupportedType,
+            ..
+        }) => varyings.skipped.push(varying_path),
+        Err(error) => return Err(error),
+    }
+    Ok(())
+}
+
+fn join_path(path: &str, name: &str) -> String {
+    if path.is_empty() {
+        name.to_string()
+    } else {
+        format!("{path}.{name}")
     }
 }
 
 fn parameter_user_attributes<'a>(
     user_attributes: impl ExactSizeIterator<Item = &'a UserAttribute>,
-) -> Vec<UserAttributeReflection> {
+    path: &str,
+) -> ReflectResult<Vec<UserAttributeReflection>> {
     let mut attributes = Vec::new();
 
     for attribute in user_attributes {
@@ -193,142 +794,522 @@ fn parameter_user_attributes<'a>(
         }
 
         attributes.push(UserAttributeReflection {
-            name: attribute.name().unwrap().to_string(),
+            name: attribute
+                .name()
+                .ok_or_else(|| ReflectError::missing(path, "user attribute name"))?
+                .to_string(),
             parameters,
         })
     }
 
-    attributes
+    Ok(attributes)
 }
 
 fn bound_resource_from_slang_type(
     slang_type: &shader_slang::reflection::Type,
     slang_layout: &shader_slang::reflection::VariableLayout,
-) -> BoundParameter {
-    if matches!(slang_layout.category().unwrap(), ParameterCategory::Uniform) {
-        return BoundParameter::Uniform {
-            uniform_offset: slang_layout.offset(ParameterCategory::Uniform),
-            resource_result: reflection_type_from_slang_type(
-                slang_type,
-                slang_layout.type_layout(),
-            ),
-        };
+    path: &str,
+) -> ReflectResult<BoundParameter> {
+    let category = slang_layout
+        .category()
+        .ok_or_else(|| ReflectError::missing(path, "parameter category"))?;
+    let type_layout = slang_layout
+        .type_layout()
+        .ok_or_else(|| ReflectError::missing(path, "type layout"))?;
+    match category {
+        shader_slang::ParameterCategory::Uniform => {
+            return Ok(BoundParameter::Uniform {
+                uniform_offset: slang_layout.offset(shader_slang::ParameterCategory::Uniform),
+                resource_result: reflection_type_from_slang_type(
+                    slang_type,
+                    Some(type_layout),
+                    path,
+                )?,
+                layout: TypeLayoutReflection::from_slang_layout(type_layout),
+            });
+        }
+        shader_slang::ParameterCategory::PushConstantBuffer => {
+            // Both `ConstantBuffer<T>` and a plain struct can be marked as push constants.
+            let element_layout = if matches!(type_layout.kind(), TypeKind::ConstantBuffer) {
+                type_layout
+                    .element_type_layout()
+                    .ok_or_else(|| ReflectError::missing(path, "push constant element layout"))?
+            } else {
+                type_layout
+            };
+            return Ok(BoundParameter::PushConstants(PushConstantBlock {
+                size: element_layout.size(shader_slang::ParameterCategory::Uniform),
+                element_type: element_type_from_slang_layout(element_layout, path)?,
+                variables: reflect_variables(element_layout, path)?,
+            }));
+        }
+        shader_slang::ParameterCategory::SpecializationConstant => {
+            return Ok(BoundParameter::SpecializationConstant(
+                specialization_constant_from_slang_layout(slang_type, slang_layout, path)?,
+            ));
+        }
+        _ => {}
     }
-    match slang_type.kind() {
-        TypeKind::Resource => match slang_type.resource_shape() {
-            ResourceShape::SlangTexture1d
-            | ResourceShape::SlangTexture2d
-            | ResourceShape::SlangTexture3d
-            | ResourceShape::SlangTextureCube => BoundParameter::Resource {
-                binding_index: slang_layout.binding_index(),
-                resource: BoundResource::Texture {
-                    tex_type: resource_shape_to_tex_type(slang_type.resource_shape()),
-                    resource_result: reflection_type_from_slang_type(
-                        slang_type.resource_result_type().unwrap(),
-                        None,
-                    ),
-                    format: slang_layout.image_format(),
-                    resource_access: slang_layout.type_layout().unwrap().resource_access().unwrap(),
+    Ok(BoundParameter::Resource {
+        resource: bound_resource_from_slang_layout(slang_type, type_layout, slang_layout, path)?,
+        binding_index: slang_layout.binding_index(),
+        binding_space: slang_layout.binding_space(),
+        category: category.into(),
+    })
+}
+
+fn specialization_constant_from_slang_layout(
+    slang_type: &shader_slang::reflection::Type,
+    slang_layout: &shader_slang::reflection::VariableLayout,
+    path: &str,
+) -> ReflectResult<SpecializationConstantReflection> {
+    if !matches!(slang_type.kind(), TypeKind::Scalar) {
+        return Err(ReflectError::new(path, ReflectErrorReason::UnsupportedType)
+            .with_type_kind(slang_type.kind()));
+    }
+    let scalar_type = ScalarType::from(slang_type.scalar_type());
+    let variable = slang_layout
+        .variable()
+        .ok_or_else(|| ReflectError::missing(path, "specialization constant variable"))?;
+    let default_value = match scalar_type {
+        ScalarType::Float16 | ScalarType::Float32 | ScalarType::Float64 => variable
+            .default_value_float()
+            .map(|value| SpecializationConstantValue::Float(value as f64)),
+        ScalarType::Bool => variable
+            .default_value_int()
+            .map(|value| SpecializationConstantValue::Bool(value != 0)),
+        _ => variable
+            .default_value_int()
+            .map(SpecializationConstantValue::Int),
+    };
+    Ok(SpecializationConstantReflection {
+        constant_id: slang_layout.offset(shader_slang::ParameterCategory::SpecializationConstant)
+            as u32,
+        scalar_type,
+        default_value,
+    })
+}
+
+/// Reflects a resource of type `slang_type` laid out as `type_layout`. `slang_layout` is the
+/// layout of the enclosing variable, which differs from `type_layout` for arrays of resources.
+fn bound_resource_from_slang_layout(
+    slang_type: &shader_slang::reflection::Type,
+    type_layout: &shader_slang::reflection::TypeLayout,
+    slang_layout: &shader_slang::reflection::VariableLayout,
+    path: &str,
+) -> ReflectResult<BoundResource> {
+    let resource_access = || {
+        type_layout
+            .resource_access()
+            .map(ResourceAccess::from)
+            .ok_or_else(|| ReflectError::missing(path, "resource access"))
+    };
+    let resource = match slang_type.kind() {
+        TypeKind::Array => {
+            let element_path = format!("{path}[]");
+            let element_type = slang_type
+                .element_type()
+                .ok_or_else(|| ReflectError::missing(path, "array element type"))?;
+            let element_type_layout = type_layout
+                .element_type_layout()
+                .ok_or_else(|| ReflectError::missing(&element_path, "type layout"))?;
+            let count = match slang_type.element_count() {
+                0 | usize::MAX => DescriptorCount::Unbounded,
+                count => DescriptorCount::Fixed(count),
+            };
+            BoundResource::Array {
+                element: Box::new(bound_resource_from_slang_layout(
+                    element_type,
+                    element_type_layout,
+                    slang_layout,
+                    &element_path,
+                )?),
+                count,
+            }
+        }
+        TypeKind::DynamicResource => BoundResource::DynamicResource,
+        TypeKind::Resource => {
+            let shape = slang_type.resource_shape();
+            let has_flag = |flag: ResourceShape| shape as u32 & flag as u32 != 0;
+            let resource_result = || {
+                reflection_type_from_slang_type(
+                    slang_type
+                        .resource_result_type()
+                        .ok_or_else(|| ReflectError::missing(path, "resource result type"))?,
+                    None,
+                    path,
+                )
+            };
+            match (
+                resource_shape_to_tex_type(shape),
+                resource_base_shape(shape),
+            ) {
+                (Some(tex_type), _) if has_flag(ResourceShape::SlangTextureFeedbackFlag) => {
+                    BoundResource::FeedbackTexture {
+                        tex_type,
+                        is_array: has_flag(ResourceShape::SlangTextureArrayFlag),
+                    }
+                }
+                (Some(tex_type), _) if has_flag(ResourceShape::SlangTextureCombinedFlag) => {
+                    let (sampler_binding_index, sampler_binding_space) =
+                        combined_sampler_binding(type_layout, slang_layout);
+                    BoundResource::CombinedTextureSampler {
+                        tex_type,
+                        resource_result: resource_result()?,
+                        format: slang_layout.image_format().into(),
+                        is_array: has_flag(ResourceShape::SlangTextureArrayFlag),
+                        is_multisampled: has_flag(ResourceShape::SlangTextureMultisampleFlag),
+                        is_shadow: has_flag(ResourceShape::SlangTextureShadowFlag),
+                        sampler_binding_index,
+                        sampler_binding_space,
+                    }
+                }
+                (Some(tex_type), _) => BoundResource::Texture {
+                    tex_type,
+                    resource_result: resource_result()?,
+                    format: slang_layout.image_format().into(),
+                    resource_access: resource_access()?,
+                    is_array: has_flag(ResourceShape::SlangTextureArrayFlag),
+                    is_multisampled: has_flag(ResourceShape::SlangTextureMultisampleFlag),
+                    is_shadow: has_flag(ResourceShape::SlangTextureShadowFlag),
+                },
+                (None, ResourceShape::SlangTextureBuffer) => BoundResource::TexelBuffer {
+                    resource_result: resource_result()?,
+                    format: slang_layout.image_format().into(),
+                    resource_access: resource_access()?,
                 },
-            },
-            ResourceShape::SlangStructuredBuffer => BoundParameter::Resource {
-                binding_index: slang_layout.binding_index(),
-                resource: BoundResource::StructuredBuffer {
-                    resource_result: reflection_type_from_slang_type(
-                        slang_type.element_type().unwrap(),
-                        slang_layout.type_layout().unwrap().element_type_layout(),
-                    ),
-                    resource_access: slang_layout.type_layout().unwrap().resource_access().unwrap(),
+                (None, ResourceShape::SlangStructuredBuffer) => {
+                    let element_layout = type_layout
+                        .element_type_layout()
+                        .ok_or_else(|| ReflectError::missing(path, "element type layout"))?;
+                    let resource_result = reflection_type_from_slang_type(
+                        slang_type
+                            .element_type()
+                            .ok_or_else(|| ReflectError::missing(path, "element type"))?,
+                        Some(element_layout),
+                        &format!("{path}[]"),
+                    )?;
+                    let element_layout = TypeLayoutReflection::from_slang_layout(element_layout);
+                    match resource_access()? {
+                        resource_access @ (ResourceAccess::Append | ResourceAccess::Consume) => {
+                            BoundResource::AppendConsumeBuffer {
+                                resource_result,
+                                element_layout,
+                                resource_access,
+                                counter: type_layout.explicit_counter().map(|counter| {
+                                    CounterBinding {
+                                        binding_index: slang_layout.binding_index()
+                                            + counter.binding_index(),
+                                        binding_space: slang_layout.binding_space()
+                                            + counter.binding_space(),
+                                    }
+                                }),
+                            }
+                        }
+                        resource_access => BoundResource::StructuredBuffer {
+                            resource_result,
+                            element_layout,
+                            resource_access,
+                        },
+                    }
+                }
+                (None, ResourceShape::SlangByteAddressBuffer) => BoundResource::RawBuffer {
+                    resource_access: resource_access()?,
                 },
-            },
-            rs => {
-                panic!("{rs:?} resource shape not implemented for bound_resource_from_slang_type")
+                _ => {
+                    return Err(ReflectError::new(
+                        path,
+                        ReflectErrorReason::UnsupportedResourceShape,
+                    )
+                    .with_type_kind(TypeKind::Resource)
+                    .with_resource_shape(shape));
+                }
             }
+        }
+        kind @ (TypeKind::ConstantBuffer | TypeKind::ParameterBlock | TypeKind::TextureBuffer) => {
+            let block = uniform_block_from_slang_layout(slang_layout, type_layout, path)?;
+            match kind {
+                TypeKind::ConstantBuffer => BoundResource::ConstantBuffer(block),
+                TypeKind::ParameterBlock => BoundResource::ParameterBlock(block),
+                _ => BoundResource::TextureBuffer(block),
+            }
+        }
+        TypeKind::SamplerState => BoundResource::Sampler {
+            is_comparison: is_comparison_sampler(slang_type),
         },
-        TypeKind::SamplerState => BoundParameter::Resource {
-            binding_index: slang_layout.binding_index(),
-            resource: BoundResource::Sampler,
-        },
-        ty => panic!(
-            "{ty:?} not recognized as valid top level type category for object of type {:?}",
-            slang_type.kind()
-        ),
+        ty => {
+            return Err(
+                ReflectError::new(path, ReflectErrorReason::UnsupportedTopLevelType)
+                    .with_type_kind(ty),
+            );
+        }
+    };
+    Ok(resource)
+}
+
+/// Whether a sampler type is `SamplerComparisonState`, from the shadow flag of its resource
+/// shape. Both sampler types have the same kind, so a sampler without the flag is also checked
+/// against the builtin type it resolves to, which aliases don't change.
+fn is_comparison_sampler(slang_type: &shader_slang::reflection::Type) -> bool {
+    let shape = slang_type.resource_shape() as u32;
+    shape & ResourceShape::SlangTextureShadowFlag as u32 != 0
+        || slang_type.name() == Some("SamplerComparisonState")
+}
+
+/// Finds where the sampler half of a combined texture-sampler is bound, as `(index, space)`.
+///
+/// D3D binds the sampler in an `s` register, given by the `SamplerState` offset. Elsewhere it
+/// is one of the descriptors of the variable's binding range: a descriptor of its own after the
+/// texture's on targets without combined samplers (WGSL), or the texture's own combined
+/// descriptor (Vulkan).
+fn combined_sampler_binding(
+    type_layout: &shader_slang::reflection::TypeLayout,
+    slang_layout: &shader_slang::reflection::VariableLayout,
+) -> (u32, u32) {
+    if type_layout.size(shader_slang::ParameterCategory::SamplerState) > 0 {
+        return (
+            slang_layout.offset(shader_slang::ParameterCategory::SamplerState) as u32,
+            slang_layout.binding_space(),
+        );
     }
+    let sampler_offset = if type_layout.binding_range_count() > 0 {
+        let set = type_layout.binding_range_descriptor_set_index(0);
+        let first_range = type_layout.binding_range_first_descriptor_range_index(0);
+        let range_count = type_layout.binding_range_descriptor_range_count(0);
+        (first_range..first_range + range_count)
+            .find(|&range| {
+                matches!(
+                    type_layout.descriptor_set_descriptor_range_type(set, range),
+                    shader_slang::BindingType::Sampler
+                )
+            })
+            .map_or(0, |range| {
+                type_layout.descriptor_set_descriptor_range_index_offset(set, range)
+            })
+    } else {
+        0
+    };
+    (
+        slang_layout.binding_index() + sampler_offset as u32,
+        slang_layout.binding_space(),
+    )
+}
+
+fn uniform_block_from_slang_layout(
+    slang_layout: &shader_slang::reflection::VariableLayout,
+    type_layout: &shader_slang::reflection::TypeLayout,
+    path: &str,
+) -> ReflectResult<UniformBlock> {
+    let element_layout = type_layout
+        .element_type_layout()
+        .ok_or_else(|| ReflectError::missing(path, "block element layout"))?;
+    if !matches!(element_layout.kind(), TypeKind::Struct) {
+        return Err(ReflectError::new(path, ReflectErrorReason::UnsupportedType)
+            .with_type_kind(element_layout.kind()));
+    }
+    // A parameter block claims a whole space of its own, everything else lives in the parent's.
+    let space = if matches!(type_layout.kind(), TypeKind::ParameterBlock) {
+        slang_layout.offset(shader_slang::ParameterCategory::SubElementRegisterSpace) as u32
+    } else {
+        slang_layout.binding_space()
+    };
+    // Field bindings count from the start of `T`. The block's own uniform buffer takes the first
+    // binding when `T` has uniform data, which the element's offsets account for.
+    let mut variables = reflect_variables(element_layout, path)?;
+    if let Some(element_var_layout) = type_layout.element_var_layout() {
+        for (variable, field) in variables.iter_mut().zip(element_layout.fields()) {
+            if let (BoundParameter::Resource { binding_index, .. }, Some(category)) =
+                (&mut variable.reflection_type, binding_category(field))
+            {
+                *binding_index += element_var_layout.offset(category) as u32;
+            }
+        }
+    }
+    Ok(UniformBlock {
+        space,
+        uniform_size: element_layout.size(shader_slang::ParameterCategory::Uniform),
+        element_type: element_type_from_slang_layout(element_layout, path)?,
+        variables,
+    })
+}
+
+/// The kind of slot the binding index of `field` counts. A field taking slots of several kinds,
+/// e.g. a combined texture-sampler on D3D, is bound by the first kind in its category list.
+fn binding_category(
+    field: &shader_slang::reflection::VariableLayout,
+) -> Option<shader_slang::ParameterCategory> {
+    match field.category()? {
+        shader_slang::ParameterCategory::Mixed => {
+            let type_layout = field.type_layout()?;
+            (type_layout.category_count() > 0).then(|| type_layout.category_by_index(0))
+        }
+        category => Some(category),
+    }
+}
+
+/// Reflects the struct `T` of a `ConstantBuffer<T>`-like block from its layout.
+fn element_type_from_slang_layout(
+    element_layout: &shader_slang::reflection::TypeLayout,
+    path: &str,
+) -> ReflectResult<VariableReflectionType> {
+    let element_type = element_layout
+        .ty()
+        .ok_or_else(|| ReflectError::missing(path, "block element type"))?;
+    reflection_type_from_slang_type(element_type, Some(element_layout), path)
 }
 
 fn reflection_type_from_slang_type(
     slang_type: &shader_slang::reflection::Type,
     slang_layout: Option<&shader_slang::reflection::TypeLayout>,
-) -> VariableReflectionType {
+    path: &str,
+) -> ReflectResult<VariableReflectionType> {
+    let require_layout = || slang_layout.ok_or_else(|| ReflectError::missing(path, "type layout"));
     match slang_type.kind() {
-        TypeKind::None => panic!("Unrecognized variable type"),
         TypeKind::Struct => {
-            if slang_type.name().unwrap() == "Atomic" {
-                return reflection_type_from_slang_type(
-                    slang_layout.unwrap().ty().unwrap(),
-                    slang_layout,
-                );
+            let name = slang_type
+                .name()
+                .ok_or_else(|| ReflectError::missing(path, "struct name"))?;
+            if name == "Atomic" {
+                let inner_type = slang_layout
+                    .and_then(|l| l.ty())
+                    .ok_or_else(|| ReflectError::missing(path, "atomic type layout"))?;
+                return reflection_type_from_slang_type(inner_type, slang_layout, path);
             }
-            let layout_fields = slang_layout
-                .iter()
-                .flat_map(|l| l.fields().map(Option::from))
-                .chain(std::iter::repeat(None));
-
-            let user_attributes = parameter_user_attributes(slang_type.user_attributes());
-            VariableReflectionType::Struct {
-                name: slang_type.name().unwrap().to_string(),
-                props: slang_type
-                    .fields()
-                    .zip(layout_fields)
-                    .map(|(type_field, layout_field)| {
-                        (
-                            type_field.name().unwrap().to_string(),
-                            reflection_type_from_slang_type(
-                                type_field.ty().unwrap(),
-                                layout_field.map(|l| l.type_layout().unwrap()),
-                            ),
+            let struct_layout = require_layout()?;
+
+            let user_attributes = parameter_user_attributes(slang_type.user_attributes(), path)?;
+            let props = slang_type
+                .fields()
+                .zip(struct_layout.fields())
+                // Resources in a block's struct are bound on their own, not stored in it.
+                .filter(|(_, layout_field)| {
+                    matches!(
+                        layout_field.category(),
+                        None | Some(
+                            shader_slang::ParameterCategory::Uniform
+                                | shader_slang::ParameterCategory::Mixed
                         )
+                    )
+                })
+                .map(|(type_field, layout_field)| {
+                    let field_name = type_field
+                        .name()
+                        .ok_or_else(|| ReflectError::missing(path, "field name"))?;
+                    let field_path = format!("{path}.{field_name}");
+                    let field_type = type_field
+                        .ty()
+                        .ok_or_else(|| ReflectError::missing(&field_path, "type"))?;
+                    let field_layout = layout_field
+                        .type_layout()
+                        .ok_or_else(|| ReflectError::missing(&field_path, "type layout"))?;
+                    Ok(StructFieldReflection {
+                        name: field_name.to_string(),
+                        offset: layout_field.offset(shader_slang::ParameterCategory::Uniform),
+                        layout: TypeLayoutReflection::from_slang_layout(field_layout),
+                        reflection_type: reflection_type_from_slang_type(
+                            field_type,
+                            Some(field_layout),
+                            &field_path,
+                        )?,
                     })
-                    .collect::<Vec<_>>(),
+                })
+                .collect::<ReflectResult<Vec<_>>>()?;
+            Ok(VariableReflectionType::Struct {
+                name: name.to_string(),
+                props,
                 user_attributes,
-            }
+                layout: TypeLayoutReflection::from_slang_layout(struct_layout),
+            })
         }
-        TypeKind::Array => VariableReflectionType::Array(
-            Box::new(reflection_type_from_slang_type(
-                slang_type.element_type().unwrap(),
-                slang_layout.map(|l| l.element_type_layout().unwrap()),
-            )),
-            slang_type.element_count(),
-        ),
-        TypeKind::Matrix => todo!(),
-        TypeKind::Vector => VariableReflectionType::Vector(
-            slang_type.element_type().unwrap().scalar_type(),
+        TypeKind::Array => {
+            let element_path = format!("{path}[]");
+            let array_layout = require_layout()?;
+            let element_type = slang_type
+                .element_type()
+                .ok_or_else(|| ReflectError::missing(path, "array element type"))?;
+            let element_type_layout = array_layout
+                .element_type_layout()
+                .ok_or_else(|| ReflectError::missing(&element_path, "type layout"))?;
+            Ok(VariableReflectionType::Array {
+                element: Box::new(reflection_type_from_slang_type(
+                    element_type,
+                    Some(element_type_layout),
+                    &element_path,
+                )?),
+                count: slang_type.element_count(),
+                element_layout: TypeLayoutReflection {
+                    stride: array_layout.element_stride(shader_slang::ParameterCategory::Uniform),
+                    ..TypeLayoutReflection::from_slang_layout(element_type_layout)
+                },
+            })
+        }
+        TypeKind::Vector => Ok(VariableReflectionType::Vector(
+            slang_type
+                .element_type()
+                .ok_or_else(|| ReflectError::missing(path, "vector element type"))?
+                .scalar_type()
+                .into(),
             slang_type.element_count(),
-        ),
-        TypeKind::Scalar => VariableReflectionType::Scalar(slang_type.scalar_type()),
-        TypeKind::ConstantBuffer => todo!(),
-        TypeKind::TextureBuffer => todo!(),
-        TypeKind::ShaderStorageBuffer => todo!(),
-        TypeKind::ParameterBlock => todo!(),
-        TypeKind::GenericTypeParameter => todo!(),
-        TypeKind::Interface => todo!(),
-        TypeKind::OutputStream => todo!(),
-        TypeKind::MeshOutput => todo!(),
-        TypeKind::Specialized => todo!(),
-        TypeKind::Feedback => todo!(),
-        TypeKind::Pointer => todo!(),
-        TypeKind::DynamicResource => todo!(),
-        TypeKind::Count => todo!(),
-        _ => panic!("Unrecognized variable type"),
+        )),
+        TypeKind::Matrix => {
+            let matrix_layout = require_layout()?;
+            let scalar = slang_type
+                .element_type()
+                .ok_or_else(|| ReflectError::missing(path, "matrix element type"))?
+                .scalar_type()
+                .into();
+            let rows = slang_type.row_count() as usize;
+            let columns = slang_type.column_count() as usize;
+            let (layout, vector_count) = match matrix_layout.matrix_layout_mode() {
+                MatrixLayoutMode::RowMajor => (MatrixLayout::RowMajor, rows),
+                _ => (MatrixLayout::ColumnMajor, columns),
+            };
+            // Some rules (e.g. D3D constant buffers) leave the last vector unpadded, so round the
+            // size up to the alignment before splitting it into vectors.
+            let TypeLayoutReflection {
+                size, alignment, ..
+            } = TypeLayoutReflection::from_slang_layout(matrix_layout);
+            Ok(VariableReflectionType::Matrix {
+                scalar,
+                rows,
+                columns,
+                layout,
+                stride: size.next_multiple_of(alignment.max(1)) / vector_count.max(1),
+            })
+        }
+        TypeKind::Scalar => Ok(VariableReflectionType::Scalar(
+            slang_type.scalar_type().into(),
+        )),
+        ty => Err(ReflectError::new(path, ReflectErrorReason::UnsupportedType).with_type_kind(ty)),
     }
 }
 
-fn resource_shape_to_tex_type(resource_shape: ResourceShape) -> TextureType {
-    match resource_shape {
-        ResourceShape::SlangTexture1d => TextureType::Dim1,
-        ResourceShape::SlangTexture2d => TextureType::Dim2,
-        ResourceShape::SlangTexture3d => TextureType::Dim3,
-        ResourceShape::SlangTextureCube => TextureType::Cube,
-        _ => todo!(),
+/// Strips the array, multisample, shadow, feedback and combined flags off a resource shape.
+fn resource_base_shape(resource_shape: ResourceShape) -> ResourceShape {
+    let base = resource_shape as u32 & ResourceShape::SlangResourceBaseShapeMask as u32;
+    [
+        ResourceShape::SlangTexture1d,
+        ResourceShape::SlangTexture2d,
+        ResourceShape::SlangTexture3d,
+        ResourceShape::SlangTextureCube,
+        ResourceShape::SlangTextureBuffer,
+        ResourceShape::SlangStructuredBuffer,
+        ResourceShape::SlangByteAddressBuffer,
+        ResourceShape::SlangResourceUnknown,
+        ResourceShape::SlangAccelerationStructure,
+        ResourceShape::SlangTextureSubpass,
+    ]
+    .into_iter()
+    .find(|shape| *shape as u32 == base)
+    .unwrap_or(ResourceShape::SlangResourceNone)
+}
+
+fn resource_shape_to_tex_type(resource_shape: ResourceShape) -> Option<TextureType> {
+    match resource_base_shape(resource_shape) {
+        ResourceShape::SlangTexture1d => Some(TextureType::Dim1),
+        ResourceShape::SlangTexture2d => Some(TextureType::Dim2),
+        ResourceShape::SlangTexture3d => Some(TextureType::Dim3),
+        ResourceShape::SlangTextureCube => Some(TextureType::Cube),
+        _ => None,
     }
 }
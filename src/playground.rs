@@ -0,0 +1,989 @@
+//! Typed decoding of the `playground::` attributes declared by `playground.slang`, e.g.
+//! `[playground::RAND(131072)]` on a buffer or `[playground::CALL::SIZE_OF("buf")]` on an entry
+//! point.
+//!
+//! Slang reflects `playground::CALL::SIZE_OF("buf")` as a user attribute named
+//! `playground_CALL_SIZE_OF` with untyped arguments; [`attributes`] turns those into
+//! [`PlaygroundAttribute`]s and reports the ones whose arguments don't match.
+//! [`resource_plan`] goes on to list the buffers, textures and uniforms a host has to create
+//! for them, and [`dispatch_plan`] the compute passes to run.
+
+use std::fmt;
+
+use crate::{
+    BoundParameter, BoundResource, EntrypointReflection, ImageFormat, ProgramReflection,
+    ResourceAccess, TextureType, UserAttributeParameter, UserAttributeReflection,
+    VariableReflection,
+};
+
+const PREFIX: &str = "playground_";
+
+#[cfg_attr(feature = "derive-serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub enum PlaygroundAttribute {
+    /// `ZEROS(count)`: a `float` buffer of `count` zeros.
+    Zeros { count: u32 },
+    /// `BLACK(width, height)`: a `float` texture of zeros.
+    Black { width: u32, height: u32 },
+    /// `BLACK_3D(x, y, z)`: a 3D `float` texture of zeros.
+    Black3d { size: [u32; 3] },
+    /// `BLACK_SCREEN(width_scale, height_scale)`: a `float` texture of zeros, sized relative to
+    /// the screen.
+    BlackScreen { width_scale: f32, height_scale: f32 },
+    /// `URL(url)`: a texture loaded from an image.
+    Url(String),
+    /// `RAND(count)`: a `float` buffer of `count` uniform random floats between 0 and 1.
+    Rand { count: u32 },
+    /// `SAMPLER`: a default sampler.
+    Sampler,
+    /// `TIME`: the current time in milliseconds.
+    Time,
+    /// `DELTA_TIME`: the time since the previous frame.
+    DeltaTime,
+    /// `MOUSE_POSITION`: the mouse position and button state.
+    MousePosition,
+    /// `KEY_INPUT(key)`: whether `key` is pressed.
+    KeyInput(String),
+    /// `SLIDER(default, min, max)`: a `float` controlled by a slider.
+    Slider { default: f32, min: f32, max: f32 },
+    /// `COLOR_PICK(red, green, blue)`: a `float3` color controlled by a color picker.
+    ColorPick { default: [f32; 3] },
+    /// `CALL(x, y, z)`: dispatch a grid of `x * y * z` threads.
+    Call { threads: [u32; 3] },
+    /// `CALL::SIZE_OF(resource)`: dispatch one thread per element of `resource`.
+    CallSizeOf(String),
+    /// `CALL::ONCE`: dispatch only once, at the start of rendering.
+    CallOnce,
+    /// `CALL::INDIRECT(buffer, offset)`: dispatch with arguments read from `buffer`.
+    CallIndirect { buffer: String, offset: u32 },
+    /// `REBIND_FOR_DRAW(texture)`: bind the texture created for `texture` for drawing.
+    RebindForDraw(String),
+    /// `DRAW(vertex_count, fragment_entry_point)`: draw `vertex_count` vertices with this vertex
+    /// shader and the named fragment shader.
+    Draw {
+        vertex_count: u32,
+        fragment_entry_point: String,
+    },
+    /// `MODEL(path)`: a buffer of vertices loaded from a model file.
+    Model(String),
+    /// `ANNOTATION(text)`: a Rust attribute for the struct generated by
+    /// [`RustCodegen`](crate::RustCodegen).
+    Annotation(String),
+}
+
+impl PlaygroundAttribute {
+    /// Decodes a reflected user attribute. Attributes whose name doesn't start with
+    /// `playground_` are not playground attributes and give `Ok(None)`.
+    ///
+    /// Fails with a message for unknown playground attributes and for wrong arguments.
+    pub fn from_user_attribute(
+        attribute: &UserAttributeReflection,
+    ) -> Result<Option<Self>, String> {
+        let Some(name) = attribute.name.strip_prefix(PREFIX) else {
+            return Ok(None);
+        };
+        let arguments = Arguments {
+            attribute: source_name(name),
+            parameters: &attribute.parameters,
+        };
+        let attribute = match name {
+            "ZEROS" => {
+                arguments.expect_count(1)?;
+                PlaygroundAttribute::Zeros {
+                    count: arguments.unsigned(0)?,
+                }
+            }
+            "BLACK" => {
+                arguments.expect_count(2)?;
+                PlaygroundAttribute::Black {
+                    width: arguments.unsigned(0)?,
+                    height: arguments.unsigned(1)?,
+                }
+            }
+            "BLACK_3D" => {
+                arguments.expect_count(3)?;
+                PlaygroundAttribute::Black3d {
+                    size: [
+                        arguments.unsigned(0)?,
+                        arguments.unsigned(1)?,
+                        arguments.unsigned(2)?,
+                    ],
+                }
+            }
+            "BLACK_SCREEN" => {
+                arguments.expect_count(2)?;
+                PlaygroundAttribute::BlackScreen {
+                    width_scale: arguments.float(0)?,
+                    height_scale: arguments.float(1)?,
+                }
+            }
+            "URL" => {
+                arguments.expect_count(1)?;
+                PlaygroundAttribute::Url(arguments.string(0)?)
+            }
+            "RAND" => {
+                arguments.expect_count(1)?;
+                PlaygroundAttribute::Rand {
+                    count: arguments.unsigned(0)?,
+                }
+            }
+            "SAMPLER" => {
+                arguments.expect_count(0)?;
+                PlaygroundAttribute::Sampler
+            }
+            "TIME" => {
+                arguments.expect_count(0)?;
+                PlaygroundAttribute::Time
+            }
+            "DELTA_TIME" => {
+                arguments.expect_count(0)?;
+                PlaygroundAttribute::DeltaTime
+            }
+            "MOUSE_POSITION" => {
+                arguments.expect_count(0)?;
+                PlaygroundAttribute::MousePosition
+            }
+            "KEY_INPUT" => {
+                arguments.expect_count(1)?;
+                PlaygroundAttribute::KeyInput(arguments.string(0)?)
+            }
+            "SLIDER" => {
+                arguments.expect_count(3)?;
+                PlaygroundAttribute::Slider {
+                    default: arguments.float(0)?,
+                    min: arguments.float(1)?,
+                    max: arguments.float(2)?,
+                }
+            }
+            "COLOR_PICK" => {
+                arguments.expect_count(3)?;
+                PlaygroundAttribute::ColorPick {
+                    default: [
+                        arguments.float(0)?,
+                        arguments.float(1)?,
+                        arguments.float(2)?,
+                    ],
+                }
+            }
+            "CALL" => {
+                arguments.expect_count(3)?;
+                PlaygroundAttribute::Call {
+                    threads: [
+                        arguments.unsigned(0)?,
+                        arguments.unsigned(1)?,
+                        arguments.unsigned(2)?,
+                    ],
+                }
+            }
+            "CALL_SIZE_OF" => {
+                arguments.expect_count(1)?;
+                PlaygroundAttribute::CallSizeOf(arguments.string(0)?)
+            }
+            "CALL_ONCE" => {
+                arguments.expect_count(0)?;
+                PlaygroundAttribute::CallOnce
+            }
+            "CALL_INDIRECT" => {
+                arguments.expect_count(2)?;
+                PlaygroundAttribute::CallIndirect {
+                    buffer: arguments.string(0)?,
+                    offset: arguments.unsigned(1)?,
+                }
+            }
+            "REBIND_FOR_DRAW" => {
+                arguments.expect_count(1)?;
+                PlaygroundAttribute::RebindForDraw(arguments.string(0)?)
+            }
+            "DRAW" => {
+                arguments.expect_count(2)?;
+                PlaygroundAttribute::Draw {
+                    vertex_count: arguments.unsigned(0)?,
+                    fragment_entry_point: arguments.string(1)?,
+                }
+            }
+            "MODEL" => {
+                arguments.expect_count(1)?;
+                PlaygroundAttribute::Model(arguments.string(0)?)
+            }
+            "ANNOTATION" => {
+                arguments.expect_count(1)?;
+                PlaygroundAttribute::Annotation(arguments.string(0)?)
+            }
+            _ => return Err(format!("unknown attribute `{}`", arguments.attribute)),
+        };
+        Ok(Some(attribute))
+    }
+
+    /// The attribute as written in Slang, e.g. `playground::CALL::SIZE_OF`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            PlaygroundAttribute::Zeros { .. } => "playground::ZEROS",
+            PlaygroundAttribute::Black { .. } => "playground::BLACK",
+            PlaygroundAttribute::Black3d { .. } => "playground::BLACK_3D",
+            PlaygroundAttribute::BlackScreen { .. } => "playground::BLACK_SCREEN",
+            PlaygroundAttribute::Url(_) => "playground::URL",
+            PlaygroundAttribute::Rand { .. } => "playground::RAND",
+            PlaygroundAttribute::Sampler => "playground::SAMPLER",
+            PlaygroundAttribute::Time => "playground::TIME",
+            PlaygroundAttribute::DeltaTime => "playground::DELTA_TIME",
+            PlaygroundAttribute::MousePosition => "playground::MOUSE_POSITION",
+            PlaygroundAttribute::KeyInput(_) => "playground::KEY_INPUT",
+            PlaygroundAttribute::Slider { .. } => "playground::SLIDER",
+            PlaygroundAttribute::ColorPick { .. } => "playground::COLOR_PICK",
+            PlaygroundAttribute::Call { .. } => "playground::CALL",
+            PlaygroundAttribute::CallSizeOf(_) => "playground::CALL::SIZE_OF",
+            PlaygroundAttribute::CallOnce => "playground::CALL::ONCE",
+            PlaygroundAttribute::CallIndirect { .. } => "playground::CALL::INDIRECT",
+            PlaygroundAttribute::RebindForDraw(_) => "playground::REBIND_FOR_DRAW",
+            PlaygroundAttribute::Draw { .. } => "playground::DRAW",
+            PlaygroundAttribute::Model(_) => "playground::MODEL",
+            PlaygroundAttribute::Annotation(_) => "playground::ANNOTATION",
+        }
+    }
+}
+
+/// A variable or entry point and the playground attributes on it.
+#[cfg_attr(feature = "derive-serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+pub struct AttributedItem {
+    pub name: String,
+    pub attributes: Vec<PlaygroundAttribute>,
+}
+
+/// The playground attributes of a program.
+#[cfg_attr(feature = "derive-serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Default)]
+pub struct PlaygroundAttributes {
+    /// Global variables with at least one playground attribute, in declaration order.
+    pub variables: Vec<AttributedItem>,
+    /// Entry points with at least one playground attribute.
+    pub entry_points: Vec<AttributedItem>,
+    /// Attributes that couldn't be decoded; they are left out of `variables` and
+    /// `entry_points`.
+    pub diagnostics: Vec<PlaygroundDiagnostic>,
+}
+
+impl PlaygroundAttributes {
+    /// The attributes of the global variable `name`, empty if it has none.
+    pub fn variable(&self, name: &str) -> &[PlaygroundAttribute] {
+        find(&self.variables, name)
+    }
+
+    /// The attributes of the entry point `name`, empty if it has none.
+    pub fn entry_point(&self, name: &str) -> &[PlaygroundAttribute] {
+        find(&self.entry_points, name)
+    }
+}
+
+fn find<'a>(items: &'a [AttributedItem], name: &str) -> &'a [PlaygroundAttribute] {
+    items
+        .iter()
+        .find(|item| item.name == name)
+        .map(|item| item.attributes.as_slice())
+        .unwrap_or_default()
+}
+
+/// A playground attribute with wrong arguments, with a name the playground doesn't know, or on a
+/// variable of the wrong kind.
+#[cfg_attr(feature = "derive-serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PlaygroundDiagnostic {
+    /// Name of the variable or entry point the attribute is on.
+    pub path: String,
+    pub message: String,
+}
+
+impl fmt::Display for PlaygroundDiagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`{}`: {}", self.path, self.message)
+    }
+}
+
+/// Decodes the playground attributes of the global variables and entry points of `program`.
+pub fn attributes(program: &ProgramReflection) -> PlaygroundAttributes {
+    let mut attributes = PlaygroundAttributes::default();
+    for variable in &program.variables {
+        if let Some(item) = decode(
+            &variable.name,
+            &variable.user_attributes,
+            &mut attributes.diagnostics,
+        ) {
+            attributes.variables.push(item);
+        }
+    }
+    for entry_point in &program.entry_points {
+        if let Some(item) = decode(
+            &entry_point.name,
+            &entry_point.user_attributes,
+            &mut attributes.diagnostics,
+        ) {
+            attributes.entry_points.push(item);
+        }
+    }
+    attributes
+}
+
+fn decode(
+    name: &str,
+    user_attributes: &[UserAttributeReflection],
+    diagnostics: &mut Vec<PlaygroundDiagnostic>,
+) -> Option<AttributedItem> {
+    let mut attributes = Vec::new();
+    for attribute in user_attributes {
+        match PlaygroundAttribute::from_user_attribute(attribute) {
+            Ok(Some(attribute)) => attributes.push(attribute),
+            Ok(None) => {}
+            Err(message) => diagnostics.push(PlaygroundDiagnostic {
+                path: name.to_string(),
+                message,
+            }),
+        }
+    }
+    (!attributes.is_empty()).then(|| AttributedItem {
+        name: name.to_string(),
+        attributes,
+    })
+}
+
+/// What a host has to create and update for a program, as requested by the playground
+/// attributes on its global variables.
+#[cfg_attr(feature = "derive-serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Default)]
+pub struct ResourcePlan {
+    pub buffers: Vec<BufferPlan>,
+    /// Buffers of vertices loaded from a model file.
+    pub models: Vec<ModelPlan>,
+    pub textures: Vec<TexturePlan>,
+    /// Textures bound, when drawing, to the texture created for another variable.
+    pub aliases: Vec<TextureAlias>,
+    /// Names of the samplers to create with default settings.
+    pub samplers: Vec<String>,
+    /// Uniforms the host writes into the global uniform buffer.
+    pub uniforms: Vec<UniformPlan>,
+    /// Size in bytes of the global uniform buffer, zero if there are no global uniforms.
+    pub uniform_buffer_size: usize,
+    /// Attributes that couldn't be decoded or don't fit their variable; they are left out of
+    /// the plan.
+    pub diagnostics: Vec<PlaygroundDiagnostic>,
+}
+
+impl ResourcePlan {
+    pub fn buffer(&self, name: &str) -> Option<&BufferPlan> {
+        self.buffers.iter().find(|b| b.name == name)
+    }
+
+    pub fn texture(&self, name: &str) -> Option<&TexturePlan> {
+        self.textures.iter().find(|t| t.name == name)
+    }
+
+    pub fn uniform(&self, name: &str) -> Option<&UniformPlan> {
+        self.uniforms.iter().find(|u| u.name == name)
+    }
+}
+
+#[cfg_attr(feature = "derive-serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct BufferPlan {
+    pub name: String,
+    pub element_count: u32,
+    /// Stride in bytes of one element.
+    pub element_size: usize,
+    /// Size in bytes of the whole buffer.
+    pub size: u64,
+    pub contents: BufferContents,
+}
+
+#[cfg_attr(feature = "derive-serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BufferContents {
+    /// `ZEROS(count)`
+    Zeros,
+    /// `RAND(count)`: uniform random floats between 0 and 1.
+    Random,
+}
+
+#[cfg_attr(feature = "derive-serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct ModelPlan {
+    pub name: String,
+    pub path: String,
+    /// Stride in bytes of one vertex; the vertex count is the model's.
+    pub element_size: usize,
+}
+
+#[cfg_attr(feature = "derive-serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+pub struct TexturePlan {
+    pub name: String,
+    pub source: TextureSource,
+    pub tex_type: TextureType,
+    pub format: ImageFormat,
+    pub resource_access: ResourceAccess,
+}
+
+#[cfg_attr(feature = "derive-serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub enum TextureSource {
+    /// `BLACK` or `BLACK_3D`: zeros, with a fixed width, height and depth.
+    Black { size: [u32; 3] },
+    /// `BLACK_SCREEN`: zeros, sized as a fraction of the screen and recreated when the screen
+    /// is resized.
+    Screen { width_scale: f32, height_scale: f32 },
+    /// `URL`: an image, whose size is the texture's.
+    Url(String),
+}
+
+impl TextureSource {
+    /// Size of the texture for a screen of `screen_width` by `screen_height` pixels; `None` for
+    /// images, whose size is only known once loaded.
+    pub fn size(&self, screen_width: u32, screen_height: u32) -> Option<[u32; 3]> {
+        match self {
+            TextureSource::Black { size } => Some(*size),
+            TextureSource::Screen {
+                width_scale,
+                height_scale,
+            } => Some([
+                ((screen_width as f32 * width_scale) as u32).max(1),
+                ((screen_height as f32 * height_scale) as u32).max(1),
+                1,
+            ]),
+            TextureSource::Url(_) => None,
+        }
+    }
+}
+
+/// `REBIND_FOR_DRAW(original)` on `name`.
+#[cfg_attr(feature = "derive-serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TextureAlias {
+    pub name: String,
+    pub original: String,
+}
+
+#[cfg_attr(feature = "derive-serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct UniformPlan {
+    pub name: String,
+    /// Byte offset in the global uniform buffer.
+    pub offset: usize,
+    pub size: usize,
+    pub source: UniformSource,
+}
+
+#[cfg_attr(feature = "derive-serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub enum UniformSource {
+    /// Written every frame with the time in milliseconds.
+    Time,
+    /// Written every frame with the time since the previous frame.
+    DeltaTime,
+    /// Written every frame with the mouse position and button state.
+    MousePosition,
+    /// Written every frame with whether the key is pressed.
+    KeyInput(String),
+    /// Controlled by a slider.
+    Slider { default: f32, min: f32, max: f32 },
+    /// Controlled by a color picker.
+    ColorPick { default: [f32; 3] },
+}
+
+impl UniformSource {
+    /// Whether the value changes from frame to frame rather than when the user edits it.
+    pub fn is_per_frame(&self) -> bool {
+        matches!(
+            self,
+            UniformSource::Time
+                | UniformSource::DeltaTime
+                | UniformSource::MousePosition
+                | UniformSource::KeyInput(_)
+        )
+    }
+}
+
+/// The resources the playground attributes of `program` ask for.
+///
+/// Attributes on a variable of the wrong kind, e.g. `RAND` on a texture, and aliases of
+/// textures that aren't in the plan are reported in [`ResourcePlan::diagnostics`].
+pub fn resource_plan(program: &ProgramReflection) -> ResourcePlan {
+    let attributes = attributes(program);
+    let mut plan = ResourcePlan {
+        uniform_buffer_size: program
+            .global_uniform_buffer
+            .as_ref()
+            .map_or(0, |buffer| buffer.size),
+        diagnostics: attributes.diagnostics,
+        ..ResourcePlan::default()
+    };
+
+    for item in &attributes.variables {
+        let Some(variable) = program.variables.iter().find(|v| v.name == item.name) else {
+            continue;
+        };
+        for attribute in &item.attributes {
+            if let Err(message) = plan.add(variable, attribute) {
+                plan.diagnostics.push(PlaygroundDiagnostic {
+                    path: variable.name.clone(),
+                    message,
+                });
+            }
+        }
+    }
+
+    let dangling = plan
+        .aliases
+        .iter()
+        .filter(|alias| plan.texture(&alias.original).is_none())
+        .map(|alias| PlaygroundDiagnostic {
+            path: alias.name.clone(),
+            message: format!(
+                "`playground::REBIND_FOR_DRAW` refers to `{}`, which is not a playground texture",
+                alias.original
+            ),
+        })
+        .collect::<Vec<_>>();
+    plan.aliases
+        .retain(|alias| dangling.iter().all(|d| d.path != alias.name));
+    plan.diagnostics.extend(dangling);
+    plan
+}
+
+impl ResourcePlan {
+    fn add(
+        &mut self,
+        variable: &VariableReflection,
+        attribute: &PlaygroundAttribute,
+    ) -> Result<(), String> {
+        let name = variable.name.clone();
+        match attribute {
+            PlaygroundAttribute::Zeros { count } | PlaygroundAttribute::Rand { count } => {
+                let element_size = structured_buffer_stride(variable, attribute)?;
+                self.buffers.push(BufferPlan {
+                    name,
+                    element_count: *count,
+                    element_size,
+                    size: *count as u64 * element_size as u64,
+                    contents: if matches!(attribute, PlaygroundAttribute::Zeros { .. }) {
+                        BufferContents::Zeros
+                    } else {
+                        BufferContents::Random
+                    },
+                });
+            }
+            PlaygroundAttribute::Model(path) => {
+                let element_size = structured_buffer_stride(variable, attribute)?;
+                self.models.push(ModelPlan {
+                    name,
+                    path: path.clone(),
+                    element_size,
+                });
+            }
+            PlaygroundAttribute::Black { width, height } => {
+                self.add_texture(
+                    variable,
+                    attribute,
+                    TextureSource::Black {
+                        size: [*width, *height, 1],
+                    },
+                )?;
+            }
+            PlaygroundAttribute::Black3d { size } => {
+                self.add_texture(variable, attribute, TextureSource::Black { size: *size })?;
+            }
+            PlaygroundAttribute::BlackScreen {
+                width_scale,
+                height_scale,
+            } => {
+                self.add_texture(
+                    variable,
+                    attribute,
+                    TextureSource::Screen {
+                        width_scale: *width_scale,
+                        height_scale: *height_scale,
+                    },
+                )?;
+            }
+            PlaygroundAttribute::Url(url) => {
+                self.add_texture(variable, attribute, TextureSource::Url(url.clone()))?;
+            }
+            PlaygroundAttribute::RebindForDraw(original) => {
+                texture(variable, attribute)?;
+                self.aliases.push(TextureAlias {
+                    name,
+                    original: original.clone(),
+                });
+            }
+            PlaygroundAttribute::Sampler => match resource(variable) {
+                Some(BoundResource::Sampler { .. }) => self.samplers.push(name),
+                _ => return Err(misplaced(attribute, "a sampler")),
+            },
+            PlaygroundAttribute::Time => {
+                self.add_uniform(variable, attribute, UniformSource::Time)?
+            }
+            PlaygroundAttribute::DeltaTime => {
+                self.add_uniform(variable, attribute, UniformSource::DeltaTime)?
+            }
+            PlaygroundAttribute::MousePosition => {
+                self.add_uniform(variable, attribute, UniformSource::MousePosition)?
+            }
+            PlaygroundAttribute::KeyInput(key) => {
+                self.add_uniform(variable, attribute, UniformSource::KeyInput(key.clone()))?
+            }
+            PlaygroundAttribute::Slider { default, min, max } => self.add_uniform(
+                variable,
+                attribute,
+                UniformSource::Slider {
+                    default: *default,
+                    min: *min,
+                    max: *max,
+                },
+            )?,
+            PlaygroundAttribute::ColorPick { default } => self.add_uniform(
+                variable,
+                attribute,
+                UniformSource::ColorPick { default: *default },
+            )?,
+            // Entry point and struct attributes don't create resources.
+            PlaygroundAttribute::Call { .. }
+            | PlaygroundAttribute::CallSizeOf(_)
+            | PlaygroundAttribute::CallOnce
+            | PlaygroundAttribute::CallIndirect { .. }
+            | PlaygroundAttribute::Draw { .. }
+            | PlaygroundAttribute::Annotation(_) => {}
+        }
+        Ok(())
+    }
+
+    fn add_texture(
+        &mut self,
+        variable: &VariableReflection,
+        attribute: &PlaygroundAttribute,
+        source: TextureSource,
+    ) -> Result<(), String> {
+        let (tex_type, format, resource_access) = texture(variable, attribute)?;
+        self.textures.push(TexturePlan {
+            name: variable.name.clone(),
+            source,
+            tex_type,
+            format,
+            resource_access,
+        });
+        Ok(())
+    }
+
+    fn add_uniform(
+        &mut self,
+        variable: &VariableReflection,
+        attribute: &PlaygroundAttribute,
+        source: UniformSource,
+    ) -> Result<(), String> {
+        let BoundParameter::Uniform {
+            uniform_offset,
+            layout,
+            ..
+        } = &variable.reflection_type
+        else {
+            return Err(misplaced(attribute, "a uniform"));
+        };
+        self.uniforms.push(UniformPlan {
+            name: variable.name.clone(),
+            offset: *uniform_offset,
+            size: layout.size,
+            source,
+        });
+        Ok(())
+    }
+}
+
+/// The compute dispatches the `CALL` attributes of a program ask for.
+#[cfg_attr(feature = "derive-serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Default)]
+pub struct DispatchPlan {
+    /// Dispatches in entry point order.
+    pub dispatches: Vec<Dispatch>,
+    /// Entry points that can't be dispatched; they are left out of `dispatches`.
+    pub diagnostics: Vec<PlaygroundDiagnostic>,
+}
+
+impl DispatchPlan {
+    pub fn dispatch(&self, entry_point: &str) -> Option<&Dispatch> {
+        self.dispatches
+            .iter()
+            .find(|d| d.entry_point == entry_point)
+    }
+}
+
+#[cfg_attr(feature = "derive-serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Dispatch {
+    pub entry_point: String,
+    pub size: DispatchSize,
+    pub frequency: DispatchFrequency,
+}
+
+#[cfg_attr(feature = "derive-serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DispatchFrequency {
+    /// `CALL::ONCE`: only at the start of rendering.
+    Once,
+    EveryFrame,
+}
+
+#[cfg_attr(feature = "derive-serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, PartialEq)]
+pub enum DispatchSize {
+    /// Workgroup counts known from the attributes alone.
+    Workgroups([u32; 3]),
+    /// `CALL::SIZE_OF` a texture whose size is only known at runtime: one thread per texel,
+    /// in workgroups of `thread_group_size`.
+    PerTexel {
+        texture: String,
+        source: TextureSource,
+        thread_group_size: [u32; 3],
+    },
+    /// `CALL::INDIRECT`: workgroup counts read from `buffer` at byte `offset`.
+    Indirect { buffer: String, offset: u32 },
+}
+
+impl DispatchSize {
+    /// Workgroup counts for a screen of `screen_width` by `screen_height` pixels; `None` for
+    /// images that aren't loaded yet and for indirect dispatches.
+    pub fn workgroups(&self, screen_width: u32, screen_height: u32) -> Option<[u32; 3]> {
+        match self {
+            DispatchSize::Workgroups(workgroups) => Some(*workgroups),
+            DispatchSize::PerTexel {
+                source,
+                thread_group_size,
+                ..
+            } => source
+                .size(screen_width, screen_height)
+                .map(|size| workgroup_count(size, *thread_group_size)),
+            DispatchSize::Indirect { .. } => None,
+        }
+    }
+}
+
+/// The dispatches requested by `CALL`, `CALL::SIZE_OF`, `CALL::INDIRECT` and `CALL::ONCE` on
+/// the entry points of `program`, sized by the resources of `resources`.
+///
+/// References to variables that don't exist or have no playground size, entry points without
+/// `[numthreads]` and entry points with more than one `CALL` are reported in
+/// [`DispatchPlan::diagnostics`]. Attributes that couldn't be decoded are already reported by
+/// [`resource_plan`] and are skipped.
+pub fn dispatch_plan(program: &ProgramReflection, resources: &ResourcePlan) -> DispatchPlan {
+    let attributes = attributes(program);
+    let mut plan = DispatchPlan::default();
+    for item in &attributes.entry_points {
+        let Some(entry_point) = program.entry_points.iter().find(|e| e.name == item.name) else {
+            continue;
+        };
+        match dispatch(program, resources, entry_point, &item.attributes) {
+            Ok(Some(dispatch)) => plan.dispatches.push(dispatch),
+            Ok(None) => {}
+            Err(message) => plan.diagnostics.push(PlaygroundDiagnostic {
+                path: entry_point.name.clone(),
+                message,
+            }),
+        }
+    }
+    plan
+}
+
+fn dispatch(
+    program: &ProgramReflection,
+    resources: &ResourcePlan,
+    entry_point: &EntrypointReflection,
+    attributes: &[PlaygroundAttribute],
+) -> Result<Option<Dispatch>, String> {
+    let mut calls = attributes.iter().filter(|attribute| {
+        matches!(
+            attribute,
+            PlaygroundAttribute::Call { .. }
+                | PlaygroundAttribute::CallSizeOf(_)
+                | PlaygroundAttribute::CallIndirect { .. }
+        )
+    });
+    let once = attributes.contains(&PlaygroundAttribute::CallOnce);
+    let call = match (calls.next(), calls.next()) {
+        (Some(call), None) => call,
+        (Some(_), Some(_)) => return Err("only one `playground::CALL` can be used".to_string()),
+        (None, _) if once => {
+            return Err("`playground::CALL::ONCE` needs a `playground::CALL`".to_string());
+        }
+        (None, _) => return Ok(None),
+    };
+    let Some(thread_group_size) = entry_point.thread_group_size else {
+        return Err(format!(
+            "`{}` needs a `[numthreads]` entry point",
+            call.name()
+        ));
+    };
+    let thread_group_size = thread_group_size.map(|size| size as u32);
+
+    let size = match call {
+        PlaygroundAttribute::Call { threads } => {
+            DispatchSize::Workgroups(workgroup_count(*threads, thread_group_size))
+        }
+        PlaygroundAttribute::CallSizeOf(name) => {
+            resolve(program, call, name)?;
+            if let Some(buffer) = resources.buffer(name) {
+                DispatchSize::Workgroups(workgroup_count(
+                    [buffer.element_count, 1, 1],
+                    thread_group_size,
+                ))
+            } else if let Some(texture) = resources.texture(name) {
+                match &texture.source {
+                    TextureSource::Black { size } => {
+                        DispatchSize::Workgroups(workgroup_count(*size, thread_group_size))
+                    }
+                    source => DispatchSize::PerTexel {
+                        texture: name.clone(),
+                        source: source.clone(),
+                        thread_group_size,
+                    },
+                }
+            } else {
+                return Err(format!(
+                    "`{}` refers to `{name}`, which has no playground size",
+                    call.name()
+                ));
+            }
+        }
+        PlaygroundAttribute::CallIndirect { buffer, offset } => {
+            resolve(program, call, buffer)?;
+            DispatchSize::Indirect {
+                buffer: buffer.clone(),
+                offset: *offset,
+            }
+        }
+        _ => unreachable!("filtered above"),
+    };
+
+    Ok(Some(Dispatch {
+        entry_point: entry_point.name.clone(),
+        size,
+        frequency: if once {
+            DispatchFrequency::Once
+        } else {
+            DispatchFrequency::EveryFrame
+        },
+    }))
+}
+
+/// Checks that the variable a `CALL` refers to exists.
+fn resolve(
+    program: &ProgramReflection,
+    call: &PlaygroundAttribute,
+    name: &str,
+) -> Result<(), String> {
+    if program.variables.iter().any(|v| v.name == name) {
+        return Ok(());
+    }
+    Err(format!(
+        "`{}` refers to `{name}`, which doesn't exist",
+        call.name()
+    ))
+}
+
+/// Workgroups needed to cover `threads`, rounding up.
+fn workgroup_count(threads: [u32; 3], thread_group_size: [u32; 3]) -> [u32; 3] {
+    [0, 1, 2].map(|i| threads[i].div_ceil(thread_group_size[i].max(1)))
+}
+
+fn resource(variable: &VariableReflection) -> Option<&BoundResource> {
+    match &variable.reflection_type {
+        BoundParameter::Resource { resource, .. } => Some(resource),
+        _ => None,
+    }
+}
+
+fn structured_buffer_stride(
+    variable: &VariableReflection,
+    attribute: &PlaygroundAttribute,
+) -> Result<usize, String> {
+    match resource(variable) {
+        Some(BoundResource::StructuredBuffer { element_layout, .. }) => Ok(element_layout.stride),
+        _ => Err(misplaced(attribute, "a structured buffer")),
+    }
+}
+
+fn texture(
+    variable: &VariableReflection,
+    attribute: &PlaygroundAttribute,
+) -> Result<(TextureType, ImageFormat, ResourceAccess), String> {
+    match resource(variable) {
+        Some(BoundResource::Texture {
+            tex_type,
+            format,
+            resource_access,
+            ..
+        }) => Ok((tex_type.clone(), *format, *resource_access)),
+        _ => Err(misplaced(attribute, "a texture")),
+    }
+}
+
+fn misplaced(attribute: &PlaygroundAttribute, expected: &str) -> String {
+    format!("`{}` can only be used on {expected}", attribute.name())
+}
+
+/// The attribute as written in Slang, e.g. `playground::CALL::SIZE_OF` for `CALL_SIZE_OF`.
+fn source_name(name: &str) -> String {
+    match name.strip_prefix("CALL_") {
+        Some(call) => format!("playground::CALL::{call}"),
+        None => format!("playground::{name}"),
+    }
+}
+
+struct Arguments<'a> {
+    attribute: String,
+    parameters: &'a [UserAttributeParameter],
+}
+
+impl Arguments<'_> {
+    fn expect_count(&self, count: usize) -> Result<(), String> {
+        if self.parameters.len() == count {
+            return Ok(());
+        }
+        Err(format!(
+            "`{}` expects {count} argument{}, found {}",
+            self.attribute,
+            if count == 1 { "" } else { "s" },
+            self.parameters.len()
+        ))
+    }
+
+    fn string(&self, index: usize) -> Result<String, String> {
+        match &self.parameters[index] {
+            UserAttributeParameter::String(value) => Ok(value.clone()),
+            _ => Err(self.mismatch(index, "a string")),
+        }
+    }
+
+    fn unsigned(&self, index: usize) -> Result<u32, String> {
+        match self.parameters[index] {
+            UserAttributeParameter::Int(value) if value >= 0 => Ok(value as u32),
+            _ => Err(self.mismatch(index, "a non-negative integer")),
+        }
+    }
+
+    /// Integer literals are accepted where a float is expected, e.g. `BLACK_SCREEN(1, 1)`.
+    fn float(&self, index: usize) -> Result<f32, String> {
+        match self.parameters[index] {
+            UserAttributeParameter::Float(value) => Ok(value),
+            UserAttributeParameter::Int(value) => Ok(value as f32),
+            UserAttributeParameter::String(_) => Err(self.mismatch(index, "a number")),
+        }
+    }
+
+    fn mismatch(&self, index: usize, expected: &str) -> String {
+        let found = match &self.parameters[index] {
+            UserAttributeParameter::String(value) => format!("{value:?}"),
+            UserAttributeParameter::Int(value) => value.to_string(),
+            UserAttributeParameter::Float(value) => value.to_string(),
+        };
+        format!(
+            "argument {} of `{}` must be {expected}, found {found}",
+            index + 1,
+            self.attribute
+        )
+    }
+}
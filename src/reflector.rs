@@ -0,0 +1,558 @@
+use std::collections::{HashSet, VecDeque};
+use std::ffi::{CStr, CString, c_void};
+use std::fmt;
+use std::mem::ManuallyDrop;
+use std::path::{Path, PathBuf};
+use std::ptr::{NonNull, null_mut};
+
+use shader_slang::{Downcast, GlobalSession, Interface, SessionDesc, TargetDesc};
+
+use crate::artifact::{EntryPointCode, ShaderArtifact, TargetArtifact};
+use crate::diagnostics::{Diagnostic, diagnostics_from_blob, diagnostics_from_error};
+use crate::file_system::VirtualFileSystem;
+use crate::{
+    CompileTarget, CompilerOptions, ComponentType, Module, ProgramLayoutReflector,
+    ProgramReflection, ReflectError, Session,
+};
+
+/// Entry point for compiling Slang modules and reflecting them without touching raw
+/// `shader_slang` handles.
+///
+/// ```no_run
+/// use slang_reflector::{CompileTarget, Reflector};
+///
+/// let reflection = Reflector::builder()
+///     .search_path("shaders")
+///     .target(CompileTarget::Wgsl)
+///     .profile("spirv_1_6")
+///     .module("cube.slang")
+///     .reflect()
+///     .unwrap();
+/// ```
+pub struct Reflector;
+
+impl Reflector {
+    pub fn builder() -> ReflectorBuilder {
+        ReflectorBuilder {
+            search_paths: Vec::new(),
+            targets: Vec::new(),
+            modules: Vec::new(),
+            virtual_files: Vec::new(),
+            options: CompilerOptions::default(),
+            pending_profile: None,
+        }
+    }
+}
+
+#[derive(Clone)]
+struct TargetConfig {
+    format: CompileTarget,
+    profile: Option<String>,
+}
+
+/// Reflection of a program for one of the builder's targets.
+#[cfg_attr(feature = "derive-serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone)]
+pub struct TargetReflection {
+    pub target: CompileTarget,
+    pub profile: Option<String>,
+    pub reflection: ProgramReflection,
+}
+
+pub struct ReflectorBuilder {
+    search_paths: Vec<String>,
+    targets: Vec<TargetConfig>,
+    modules: Vec<String>,
+    virtual_files: Vec<(String, String)>,
+    options: CompilerOptions,
+    /// Profile set before any target was added, applied to the first one.
+    pending_profile: Option<String>,
+}
+
+impl ReflectorBuilder {
+    /// Adds a directory that module names and `import`s are resolved against.
+    pub fn search_path(mut self, path: impl Into<String>) -> Self {
+        self.search_paths.push(path.into());
+        self
+    }
+
+    /// Adds a compile target. Layout rules and bindings are reflected for this target.
+    pub fn target(mut self, format: CompileTarget) -> Self {
+        self.targets.push(TargetConfig {
+            format,
+            profile: self.pending_profile.take(),
+        });
+        self
+    }
+
+    /// Sets the profile, e.g. `spirv_1_6`, of the most recently added target, or of the next
+    /// target when none was added yet.
+    pub fn profile(mut self, profile: impl Into<String>) -> Self {
+        match self.targets.last_mut() {
+            Some(target) => target.profile = Some(profile.into()),
+            None => self.pending_profile = Some(profile.into()),
+        }
+        self
+    }
+
+    /// Adds a module to load, by name or by path relative to a search path.
+    pub fn module(mut self, name: impl Into<String>) -> Self {
+        self.modules.push(name.into());
+        self
+    }
+
+    /// Adds a module whose source is held in memory, e.g. an editor buffer or an `include_str!`.
+    pub fn source(mut self, module_name: impl Into<String>, source: impl Into<String>) -> Self {
+        let module_name = module_name.into();
+        self.virtual_files
+            .push((format!("{module_name}.slang"), source.into()));
+        self.modules.push(module_name);
+        self
+    }
+
+    /// Adds a file to the virtual filesystem. Slang reads every file through it, and a virtual
+    /// file takes the place of a file on disk at the same path, relative to the working
+    /// directory. So `playground.slang` satisfies `import playground;`, `utils/math.slang`
+    /// satisfies `import utils.math;` and `#include "utils/math.slang"`, and
+    /// `shaders/common.slang` is found through the search path `shaders`.
+    pub fn virtual_file(mut self, path: impl Into<String>, contents: impl Into<String>) -> Self {
+        self.virtual_files.push((path.into(), contents.into()));
+        self
+    }
+
+    /// Adds every `(path, contents)` pair to the virtual filesystem.
+    pub fn virtual_files<P: Into<String>, C: Into<String>>(
+        mut self,
+        files: impl IntoIterator<Item = (P, C)>,
+    ) -> Self {
+        self.virtual_files.extend(
+            files
+                .into_iter()
+                .map(|(path, contents)| (path.into(), contents.into())),
+        );
+        self
+    }
+
+    /// Replaces the session wide compiler options.
+    pub fn options(mut self, options: CompilerOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Compiles and links every module along with all of its entry points, then reflects the
+    /// program for the first target.
+    pub fn reflect(&self) -> Result<ProgramReflection, ReflectorError> {
+        self.compile()?.reflect()
+    }
+
+    /// Compiles the program and bundles the code of every entry point for every target with the
+    /// target's reflection.
+    pub fn artifact(&self) -> Result<ShaderArtifact, ReflectorError> {
+        self.compile()?.artifact()
+    }
+
+    /// Compiles and links the program like [`reflect`](Self::reflect), then reflects it once
+    /// for every target, in the order the targets were added.
+    pub fn reflect_targets(&self) -> Result<Vec<TargetReflection>, ReflectorError> {
+        self.compile()?.reflect_targets()
+    }
+
+    /// Loads every module and, transitively, every module it imports, then links them together
+    /// with all of their entry points.
+    pub fn compile(&self) -> Result<CompiledProgram, ReflectorError> {
+        self.compile_modules(&self.modules, &[])
+    }
+
+    /// Like [`compile`](Self::compile), with `modules` as the root modules instead of the ones
+    /// added to the builder, and `search_paths` searched before the builder's own.
+    pub(crate) fn compile_modules(
+        &self,
+        modules: &[String],
+        search_paths: &[String],
+    ) -> Result<CompiledProgram, ReflectorError> {
+        if self.targets.is_empty() {
+            return Err(ReflectorError::NoTarget);
+        }
+        let global_session = GlobalSession::new().ok_or(ReflectorError::GlobalSession)?;
+
+        let targets = self
+            .targets
+            .iter()
+            .map(|target| {
+                let target_desc = TargetDesc::default().format(target.format.into());
+                match &target.profile {
+                    Some(profile) => target_desc.profile(global_session.find_profile(profile)),
+                    None => target_desc,
+                }
+            })
+            .collect::<Vec<_>>();
+
+        let search_paths = search_paths
+            .iter()
+            .chain(&self.search_paths)
+            .map(|path| {
+                CString::new(path.as_str())
+                    .map_err(|_| ReflectorError::InvalidSearchPath(path.clone()))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let search_paths = search_paths.iter().map(|p| p.as_ptr()).collect::<Vec<_>>();
+
+        let session_desc = SessionDesc::default()
+            .search_paths(&search_paths)
+            .targets(&targets)
+            .options(&self.options);
+        let file_system = VirtualFileSystem::new(&self.virtual_files);
+        let session = create_session(&global_session, &session_desc, &file_system)?;
+
+        let mut diagnostics = Vec::new();
+        let mut components: Vec<ComponentType> = Vec::new();
+        let mut loaded_modules = HashSet::new();
+        let mut dependency_files: Vec<PathBuf> = Vec::new();
+        let mut pending_modules = modules.iter().cloned().collect::<VecDeque<_>>();
+        while let Some(module_name) = pending_modules.pop_front() {
+            let module = load_module(&session, &module_name, &mut diagnostics)?;
+            // A module's dependencies include its own file, which resolves to the module that
+            // is already loaded.
+            if !loaded_modules.insert(module.unique_identity().to_string()) {
+                continue;
+            }
+
+            components.push(module.downcast().clone());
+
+            for entry_point in module.entry_points() {
+                components.push(entry_point.downcast().clone());
+            }
+
+            // Loading a module also loads everything it imports. Its other dependencies are
+            // files pulled in with `#include`, which are not modules of their own.
+            let module_files = loaded_module_files(&session);
+            for dependency in module.dependency_file_paths() {
+                let dependency_path = Path::new(dependency);
+                if dependency_files.iter().any(|file| file == dependency_path) {
+                    continue;
+                }
+                dependency_files.push(dependency_path.to_path_buf());
+                if module_files.contains(&normalized_path(dependency_path)) {
+                    pending_modules.push_back(dependency.to_string());
+                }
+            }
+        }
+
+        let program = session
+            .create_composite_component_type(components.as_slice())
+            .map_err(|e| ReflectorError::Link(diagnostics_from_error(&e)))?;
+        let linked_program = link(&program, &mut diagnostics)?;
+
+        Ok(CompiledProgram {
+            linked_program,
+            targets: self.targets.clone(),
+            dependency_files,
+            diagnostics,
+            _session: session,
+            _global_session: global_session,
+        })
+    }
+}
+
+/// Files of the modules `session` has loaded, including the ones loaded through `import`.
+fn loaded_module_files(session: &Session) -> HashSet<PathBuf> {
+    // SAFETY: `session` is a live `ISession`.
+    let count = unsafe { (session.vtable().getLoadedModuleCount)(session.as_raw()) };
+    (0..count)
+        .filter_map(|index| {
+            // SAFETY: `index` is in range. The module stays owned by the session and is only
+            // borrowed here, and its vtable starts with the one of `IModule`.
+            let path = unsafe {
+                let module = (session.vtable().getLoadedModule)(session.as_raw(), index);
+                if module.is_null() {
+                    return None;
+                }
+                let vtable = &**(module as *const *const shader_slang::sys::IModuleVtable);
+                (vtable.getFilePath)(module.cast())
+            };
+            // SAFETY: a non-null path is a NUL-terminated string owned by the module.
+            let path = (!path.is_null()).then(|| unsafe { CStr::from_ptr(path) })?;
+            Some(normalized_path(Path::new(path.to_str().ok()?)))
+        })
+        .collect()
+}
+
+/// `path` with symlinks and `..` resolved when it exists on disk, so paths Slang reports in
+/// different places compare equal.
+fn normalized_path(path: &Path) -> PathBuf {
+    path.canonicalize().unwrap_or_else(|_| path.to_path_buf())
+}
+
+// The raw calls return interface pointers that are transmuted into the `shader_slang` handles,
+// which must be nothing but that pointer.
+const _: () = assert!(size_of::<Session>() == size_of::<*mut c_void>());
+const _: () = assert!(size_of::<Module>() == size_of::<*mut c_void>());
+const _: () = assert!(size_of::<ComponentType>() == size_of::<*mut c_void>());
+
+/// Creates a session that reads files through `file_system`, which `shader_slang`'s
+/// `SessionDesc` has no setter for.
+fn create_session(
+    global_session: &GlobalSession,
+    session_desc: &SessionDesc,
+    file_system: &VirtualFileSystem,
+) -> Result<Session, ReflectorError> {
+    let mut desc = **session_desc;
+    desc.fileSystem = file_system.as_raw().cast();
+    let mut session = null_mut();
+    // SAFETY: `global_session` is a live `IGlobalSession` and the arguments match
+    // `createSession`. The session adds its own reference to the file system.
+    let result = unsafe {
+        (global_session.vtable().createSession)(global_session.as_raw(), &desc, &mut session)
+    };
+    match NonNull::new(session) {
+        // SAFETY: `Session` is a transparent wrapper around the interface pointer and takes over
+        // the reference `createSession` returned.
+        Some(session) if result >= 0 => {
+            Ok(unsafe { std::mem::transmute::<NonNull<_>, Session>(session) })
+        }
+        _ => Err(ReflectorError::Session),
+    }
+}
+
+// `shader_slang` only hands out the diagnostics of a call that fails, so loading and linking
+// call the Slang interfaces directly to keep the warnings of the calls that succeed.
+
+/// Loads the module `name` with `ISession::loadModule`, adding its warnings to `diagnostics`.
+fn load_module(
+    session: &Session,
+    name: &str,
+    diagnostics: &mut Vec<Diagnostic>,
+) -> Result<Module, ReflectorError> {
+    let load_error = |diagnostics| ReflectorError::LoadModule {
+        module: name.to_string(),
+        diagnostics,
+    };
+    let c_name = CString::new(name).map_err(|_| load_error(Vec::new()))?;
+    let mut blob = null_mut();
+    // SAFETY: `session` is a live `ISession` and the arguments match `loadModule`.
+    let module =
+        unsafe { (session.vtable().loadModule)(session.as_raw(), c_name.as_ptr(), &mut blob) };
+    // SAFETY: `loadModule` hands out a reference to the blob, if it returns one.
+    let module_diagnostics = unsafe { diagnostics_from_blob(blob) };
+    let Some(module) = NonNull::new(module) else {
+        return Err(load_error(module_diagnostics));
+    };
+    diagnostics.extend(module_diagnostics);
+    // SAFETY: `Module` is a transparent wrapper around the interface pointer. `loadModule`
+    // doesn't add a reference for the caller, since the session keeps the module alive, so the
+    // borrowed handle must not be dropped. Cloning it adds the reference the result owns.
+    let borrowed = ManuallyDrop::new(unsafe { std::mem::transmute::<NonNull<_>, Module>(module) });
+    Ok(Module::clone(&borrowed))
+}
+
+/// Links `program` with `IComponentType::link`, adding its warnings to `diagnostics`.
+fn link(
+    program: &ComponentType,
+    diagnostics: &mut Vec<Diagnostic>,
+) -> Result<ComponentType, ReflectorError> {
+    let mut linked = null_mut();
+    let mut blob = null_mut();
+    // SAFETY: `program` is a live `IComponentType` and the arguments match `link`.
+    let result = unsafe { (program.vtable().link)(program.as_raw(), &mut linked, &mut blob) };
+    // SAFETY: `link` hands out a reference to the blob, if it returns one.
+    let link_diagnostics = unsafe { diagnostics_from_blob(blob) };
+    match NonNull::new(linked) {
+        Some(linked) if result >= 0 => {
+            diagnostics.extend(link_diagnostics);
+            // SAFETY: `ComponentType` is a transparent wrapper around the interface pointer and
+            // takes over the reference `link` returned.
+            Ok(unsafe { std::mem::transmute::<NonNull<_>, ComponentType>(linked) })
+        }
+        _ => Err(ReflectorError::Link(link_diagnostics)),
+    }
+}
+
+/// A linked program produced by [`ReflectorBuilder::compile`].
+pub struct CompiledProgram {
+    // Fields drop in declaration order, so the program is released before the session that
+    // created it, and the session before the global session.
+    linked_program: ComponentType,
+    targets: Vec<TargetConfig>,
+    dependency_files: Vec<PathBuf>,
+    diagnostics: Vec<Diagnostic>,
+    _session: Session,
+    _global_session: GlobalSession,
+}
+
+impl CompiledProgram {
+    /// Reflects the program for the first target.
+    pub fn reflect(&self) -> Result<ProgramReflection, ReflectorError> {
+        self.reflect_target(0)
+    }
+
+    /// Reflects the program once for every target, in the order the targets were added.
+    pub fn reflect_targets(&self) -> Result<Vec<TargetReflection>, ReflectorError> {
+        self.targets
+            .iter()
+            .enumerate()
+            .map(|(index, target)| {
+                Ok(TargetReflection {
+                    target: target.format,
+                    profile: target.profile.clone(),
+                    reflection: self.reflect_target(index)?,
+                })
+            })
+            .collect()
+    }
+
+    /// Reflects the program and generates code for every entry point, for every target.
+    pub fn artifact(&self) -> Result<ShaderArtifact, ReflectorError> {
+        let targets = self
+            .reflect_targets()?
+            .into_iter()
+            .enumerate()
+            .map(|(target_index, target_reflection)| {
+                let entry_points = target_reflection
+                    .reflection
+                    .entry_points
+                    .iter()
+                    .enumerate()
+                    .map(|(entry_point_index, entry_point)| {
+                        let code = self
+                            .linked_program
+                            .entry_point_code(entry_point_index as i64, target_index as i64)
+                            .map_err(|e| ReflectorError::CodeGen {
+                                entry_point: entry_point.name.clone(),
+                                diagnostics: diagnostics_from_error(&e),
+                            })?;
+                        Ok(EntryPointCode {
+                            name: entry_point.name.clone(),
+                            code: code.as_slice().to_vec(),
+                        })
+                    })
+                    .collect::<Result<Vec<_>, ReflectorError>>()?;
+                Ok(TargetArtifact {
+                    target: target_reflection.target,
+                    profile: target_reflection.profile,
+                    reflection: target_reflection.reflection,
+                    entry_points,
+                })
+            })
+            .collect::<Result<Vec<_>, ReflectorError>>()?;
+        Ok(ShaderArtifact { targets })
+    }
+
+    fn reflect_target(&self, index: usize) -> Result<ProgramReflection, ReflectorError> {
+        let shader_reflection = self
+            .linked_program
+            .layout(index as i64)
+            .map_err(|e| ReflectorError::Layout(e.to_string()))?;
+
+        Ok(shader_reflection.try_reflect()?)
+    }
+
+    /// Every file the loaded modules were built from, including the modules themselves, their
+    /// imports and included files. Useful for emitting `cargo:rerun-if-changed` lines.
+    pub fn dependency_files(&self) -> &[PathBuf] {
+        &self.dependency_files
+    }
+
+    pub fn linked_program(&self) -> &ComponentType {
+        &self.linked_program
+    }
+
+    /// Warnings and notes Slang reported while loading and linking the program.
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        &self.diagnostics
+    }
+}
+
+/// Error produced while compiling or reflecting with a [`ReflectorBuilder`].
+#[derive(Debug, Clone)]
+pub enum ReflectorError {
+    /// No target was added to the builder.
+    NoTarget,
+    /// The search path contains an interior nul byte.
+    InvalidSearchPath(String),
+    /// The Slang global session could not be created, usually because libslang is missing.
+    GlobalSession,
+    Session,
+    LoadModule {
+        module: String,
+        diagnostics: Vec<Diagnostic>,
+    },
+    Link(Vec<Diagnostic>),
+    Layout(String),
+    CodeGen {
+        entry_point: String,
+        diagnostics: Vec<Diagnostic>,
+    },
+    Reflect(ReflectError),
+}
+
+impl ReflectorError {
+    /// Diagnostics Slang reported for the failed step, if it reported any.
+    pub fn diagnostics(&self) -> &[Diagnostic] {
+        match self {
+            ReflectorError::LoadModule { diagnostics, .. }
+            | ReflectorError::Link(diagnostics)
+            | ReflectorError::CodeGen { diagnostics, .. } => diagnostics,
+            _ => &[],
+        }
+    }
+}
+
+impl fmt::Display for ReflectorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ReflectorError::NoTarget => write!(f, "no compile target was specified"),
+            ReflectorError::InvalidSearchPath(path) => {
+                write!(f, "search path `{path}` contains a nul byte")
+            }
+            ReflectorError::GlobalSession => write!(f, "failed to create slang global session"),
+            ReflectorError::Session => write!(f, "failed to create slang session"),
+            ReflectorError::LoadModule {
+                module,
+                diagnostics,
+            } => {
+                write!(f, "failed to load module `{module}`")?;
+                write_diagnostics(f, diagnostics)
+            }
+            ReflectorError::Link(diagnostics) => {
+                write!(f, "failed to link program")?;
+                write_diagnostics(f, diagnostics)
+            }
+            ReflectorError::Layout(message) => {
+                write!(f, "failed to get program layout: {message}")
+            }
+            ReflectorError::CodeGen {
+                entry_point,
+                diagnostics,
+            } => {
+                write!(f, "failed to generate code for `{entry_point}`")?;
+                write_diagnostics(f, diagnostics)
+            }
+            ReflectorError::Reflect(error) => write!(f, "failed to reflect program: {error}"),
+        }
+    }
+}
+
+fn write_diagnostics(f: &mut fmt::Formatter<'_>, diagnostics: &[Diagnostic]) -> fmt::Result {
+    match diagnostics {
+        [] => Ok(()),
+        [diagnostic] => write!(f, ": {diagnostic}"),
+        _ => diagnostics
+            .iter()
+            .try_for_each(|diagnostic| write!(f, "\n{diagnostic}")),
+    }
+}
+
+impl std::error::Error for ReflectorError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ReflectorError::Reflect(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<ReflectError> for ReflectorError {
+    fn from(error: ReflectError) -> Self {
+        ReflectorError::Reflect(error)
+    }
+}
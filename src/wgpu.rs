@@ -0,0 +1,457 @@
+//! `wgpu` bind group layouts from a [`ProgramReflection`] reflected for
+//! [`CompileTarget::Wgsl`](crate::CompileTarget::Wgsl).
+//!
+//! # Stage visibility
+//!
+//! Reflection doesn't tell which entry point uses which global, so global resources and
+//! push-constant blocks are visible to the stages of every entry point of the pipeline. `uniform`
+//! entry point parameters, including push constants declared as entry point parameters, are only
+//! visible to the stage of their entry point.
+//!
+//! # Push-constant ranges
+//!
+//! Slang lays out every push-constant block from offset 0, and wgpu rejects ranges that share a
+//! stage. Each stage therefore gets one range from 0 to the size of the largest block it sees,
+//! and stages with ranges of the same size share one.
+
+use std::fmt;
+use std::num::{NonZeroU32, NonZeroU64};
+
+use ::wgpu::{
+    BindGroupLayoutEntry, BindingType, BufferBindingType, PushConstantRange, SamplerBindingType,
+    ShaderStages, StorageTextureAccess, TextureFormat, TextureSampleType, TextureViewDimension,
+};
+
+use crate::bindings::{Descriptor, pipeline_bindings};
+use crate::{
+    BoundResource, DescriptorCount, ImageFormat, ProgramReflection, ResourceAccess, ScalarType,
+    Stage, TextureType, VariableReflectionType,
+};
+
+/// The entries of one `@group`, sorted by binding.
+#[derive(Debug, Clone)]
+pub struct BindGroupLayoutReflection {
+    pub group: u32,
+    pub entries: Vec<BindGroupLayoutEntry>,
+    /// Dotted path of the variable bound by each entry, e.g. `material.albedo`. The global
+    /// uniform buffer is named `$Globals`, the uniform buffer of an entry point
+    /// `<entry point>.$Params`.
+    pub names: Vec<String>,
+}
+
+impl BindGroupLayoutReflection {
+    /// The entry binding the variable at `name`.
+    pub fn entry(&self, name: &str) -> Option<&BindGroupLayoutEntry> {
+        let index = self.names.iter().position(|n| n == name)?;
+        Some(&self.entries[index])
+    }
+}
+
+/// Error produced when a resource has no `wgpu` binding type.
+#[derive(Debug, Clone)]
+pub struct WgpuLayoutError {
+    /// Dotted path of the variable, e.g. `material.albedo`.
+    pub path: String,
+    pub message: String,
+}
+
+impl WgpuLayoutError {
+    fn new(path: &str, message: impl Into<String>) -> Self {
+        WgpuLayoutError {
+            path: path.to_string(),
+            message: message.into(),
+        }
+    }
+}
+
+impl fmt::Display for WgpuLayoutError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "cannot bind `{}` with wgpu: {}", self.path, self.message)
+    }
+}
+
+impl std::error::Error for WgpuLayoutError {}
+
+/// Bind group layouts for a pipeline made of every entry point of `program`.
+pub fn bind_group_layouts(
+    program: &ProgramReflection,
+) -> Result<Vec<BindGroupLayoutReflection>, WgpuLayoutError> {
+    let entry_points = program
+        .entry_points
+        .iter()
+        .map(|e| e.name.as_str())
+        .collect::<Vec<_>>();
+    bind_group_layouts_for(program, &entry_points)
+}
+
+/// Bind group layouts for a pipeline made of the named entry points, e.g. a vertex and a
+/// fragment shader. Entries are visible to the stages described in the
+/// [module documentation](crate::wgpu#stage-visibility). Push-constant blocks are not part of
+/// any group.
+pub fn bind_group_layouts_for(
+    program: &ProgramReflection,
+    entry_points: &[&str],
+) -> Result<Vec<BindGroupLayoutReflection>, WgpuLayoutError> {
+    let bindings = pipeline_bindings(program, entry_points)
+        .map_err(|name| WgpuLayoutError::new(&name, "no entry point with this name"))?;
+
+    let mut groups = Vec::new();
+    for descriptor in &bindings.descriptors {
+        let ty = match descriptor.descriptor {
+            Descriptor::Resource(resource) => binding_type(resource, &descriptor.name)?,
+            Descriptor::UniformBuffer { size } => uniform_buffer(size),
+            Descriptor::Counter => storage_buffer(false, 4),
+        };
+        let count = match descriptor.count {
+            None => None,
+            Some(DescriptorCount::Fixed(count)) => NonZeroU32::new(count as u32),
+            Some(DescriptorCount::Unbounded) => {
+                return Err(WgpuLayoutError::new(
+                    &descriptor.name,
+                    "runtime-sized binding arrays need a fixed count in wgpu",
+                ));
+            }
+        };
+        let visibility = descriptor
+            .stages
+            .iter()
+            .fold(ShaderStages::NONE, |stages, &stage| {
+                stages | shader_stages(stage)
+            });
+        let entry = BindGroupLayoutEntry {
+            binding: descriptor.binding,
+            visibility,
+            ty,
+            count,
+        };
+        insert_entry(&mut groups, descriptor.set, entry, &descriptor.name)?;
+    }
+
+    groups.sort_by_key(|group| group.group);
+    for group in &mut groups {
+        let mut bindings = std::mem::take(&mut group.names)
+            .into_iter()
+            .zip(std::mem::take(&mut group.entries))
+            .collect::<Vec<_>>();
+        bindings.sort_by_key(|(_, entry)| entry.binding);
+        (group.names, group.entries) = bindings.into_iter().unzip();
+    }
+    Ok(groups)
+}
+
+/// Push-constant ranges for a pipeline made of the named entry points, for use with
+/// `wgpu::Features::PUSH_CONSTANTS`, merged as described in the
+/// [module documentation](crate::wgpu#push-constant-ranges).
+pub fn push_constant_ranges_for(
+    program: &ProgramReflection,
+    entry_points: &[&str],
+) -> Result<Vec<PushConstantRange>, WgpuLayoutError> {
+    let bindings = pipeline_bindings(program, entry_points)
+        .map_err(|name| WgpuLayoutError::new(&name, "no entry point with this name"))?;
+
+    Ok(bindings
+        .push_constant_ranges()
+        .iter()
+        .map(|range| PushConstantRange {
+            stages: range
+                .stages
+                .iter()
+                .fold(ShaderStages::NONE, |stages, &stage| {
+                    stages | shader_stages(stage)
+                }),
+            range: 0..range.size as u32,
+        })
+        .collect())
+}
+
+/// Shader stages `wgpu` has for a Slang stage; empty for stages WebGPU doesn't support.
+pub fn shader_stages(stage: Stage) -> ShaderStages {
+    match stage {
+        Stage::Vertex => ShaderStages::VERTEX,
+        Stage::Fragment => ShaderStages::FRAGMENT,
+        Stage::Compute => ShaderStages::COMPUTE,
+        _ => ShaderStages::NONE,
+    }
+}
+
+/// `TextureFormat` for a storage texture format, `None` for formats WebGPU doesn't have.
+pub fn texture_format(format: ImageFormat) -> Option<TextureFormat> {
+    Some(match format {
+        ImageFormat::Rgba32f => TextureFormat::Rgba32Float,
+        ImageFormat::Rgba16f => TextureFormat::Rgba16Float,
+        ImageFormat::Rg32f => TextureFormat::Rg32Float,
+        ImageFormat::Rg16f => TextureFormat::Rg16Float,
+        ImageFormat::R32f => TextureFormat::R32Float,
+        ImageFormat::R16f => TextureFormat::R16Float,
+        ImageFormat::Rgba16 => TextureFormat::Rgba16Unorm,
+        ImageFormat::Rgb10A2 => TextureFormat::Rgb10a2Unorm,
+        ImageFormat::Rgba8 => TextureFormat::Rgba8Unorm,
+        ImageFormat::Rg16 => TextureFormat::Rg16Unorm,
+        ImageFormat::Rg8 => TextureFormat::Rg8Unorm,
+        ImageFormat::R16 => TextureFormat::R16Unorm,
+        ImageFormat::R8 => TextureFormat::R8Unorm,
+        ImageFormat::Rgba16Snorm => TextureFormat::Rgba16Snorm,
+        ImageFormat::Rgba8Snorm => TextureFormat::Rgba8Snorm,
+        ImageFormat::Rg16Snorm => TextureFormat::Rg16Snorm,
+        ImageFormat::Rg8Snorm => TextureFormat::Rg8Snorm,
+        ImageFormat::R16Snorm => TextureFormat::R16Snorm,
+        ImageFormat::R8Snorm => TextureFormat::R8Snorm,
+        ImageFormat::Rgba32i => TextureFormat::Rgba32Sint,
+        ImageFormat::Rgba16i => TextureFormat::Rgba16Sint,
+        ImageFormat::Rgba8i => TextureFormat::Rgba8Sint,
+        ImageFormat::Rg32i => TextureFormat::Rg32Sint,
+        ImageFormat::Rg16i => TextureFormat::Rg16Sint,
+        ImageFormat::Rg8i => TextureFormat::Rg8Sint,
+        ImageFormat::R32i => TextureFormat::R32Sint,
+        ImageFormat::R16i => TextureFormat::R16Sint,
+        ImageFormat::R8i => TextureFormat::R8Sint,
+        ImageFormat::Rgba32ui => TextureFormat::Rgba32Uint,
+        ImageFormat::Rgba16ui => TextureFormat::Rgba16Uint,
+        ImageFormat::Rgb10A2ui => TextureFormat::Rgb10a2Uint,
+        ImageFormat::Rgba8ui => TextureFormat::Rgba8Uint,
+        ImageFormat::Rg32ui => TextureFormat::Rg32Uint,
+        ImageFormat::Rg16ui => TextureFormat::Rg16Uint,
+        ImageFormat::Rg8ui => TextureFormat::Rg8Uint,
+        ImageFormat::R32ui => TextureFormat::R32Uint,
+        ImageFormat::R16ui => TextureFormat::R16Uint,
+        ImageFormat::R8ui => TextureFormat::R8Uint,
+        ImageFormat::Bgra8 => TextureFormat::Bgra8Unorm,
+        _ => return None,
+    })
+}
+
+/// View dimension of a texture binding, `None` for 1D and 3D texture arrays, which WebGPU
+/// doesn't have.
+pub fn view_dimension(tex_type: &TextureType, is_array: bool) -> Option<TextureViewDimension> {
+    Some(match (tex_type, is_array) {
+        (TextureType::Dim1, false) => TextureViewDimension::D1,
+        (TextureType::Dim2, false) => TextureViewDimension::D2,
+        (TextureType::Dim2, true) => TextureViewDimension::D2Array,
+        (TextureType::Dim3, false) => TextureViewDimension::D3,
+        (TextureType::Cube, false) => TextureViewDimension::Cube,
+        (TextureType::Cube, true) => TextureViewDimension::CubeArray,
+        (TextureType::Dim1 | TextureType::Dim3, true) => return None,
+    })
+}
+
+/// Adds `entry` to `group`. An entry that is already bound, e.g. a resource shared by two
+/// entry points, becomes visible to both stages.
+fn insert_entry(
+    groups: &mut Vec<BindGroupLayoutReflection>,
+    group: u32,
+    entry: BindGroupLayoutEntry,
+    path: &str,
+) -> Result<(), WgpuLayoutError> {
+    let index = match groups.iter().position(|g| g.group == group) {
+        Some(index) => index,
+        None => {
+            groups.push(BindGroupLayoutReflection {
+                group,
+                entries: Vec::new(),
+                names: Vec::new(),
+            });
+            groups.len() - 1
+        }
+    };
+    let group_layout = &mut groups[index];
+    match group_layout
+        .entries
+        .iter_mut()
+        .find(|e| e.binding == entry.binding)
+    {
+        Some(existing) if existing.ty == entry.ty && existing.count == entry.count => {
+            existing.visibility |= entry.visibility;
+        }
+        Some(_) => {
+            return Err(WgpuLayoutError::new(
+                path,
+                format!(
+                    "@group({group}) @binding({}) is already bound with a different type",
+                    entry.binding
+                ),
+            ));
+        }
+        None => {
+            group_layout.entries.push(entry);
+            group_layout.names.push(path.to_string());
+        }
+    }
+    Ok(())
+}
+
+fn binding_type(resource: &BoundResource, path: &str) -> Result<BindingType, WgpuLayoutError> {
+    Ok(match resource {
+        BoundResource::StructuredBuffer {
+            element_layout,
+            resource_access,
+            ..
+        } => storage_buffer(
+            matches!(resource_access, ResourceAccess::Read),
+            element_layout.stride,
+        ),
+        BoundResource::AppendConsumeBuffer { element_layout, .. } => {
+            storage_buffer(false, element_layout.stride)
+        }
+        BoundResource::RawBuffer { resource_access } => BindingType::Buffer {
+            ty: BufferBindingType::Storage {
+                read_only: matches!(resource_access, ResourceAccess::Read),
+            },
+            has_dynamic_offset: false,
+            min_binding_size: None,
+        },
+        BoundResource::ConstantBuffer(block) => uniform_buffer(block.uniform_size),
+        BoundResource::TextureBuffer(block) => storage_buffer(true, block.uniform_size),
+        BoundResource::Sampler { is_comparison } => BindingType::Sampler(if *is_comparison {
+            SamplerBindingType::Comparison
+        } else {
+            SamplerBindingType::Filtering
+        }),
+        BoundResource::Texture {
+            tex_type,
+            resource_result,
+            format,
+            resource_access,
+            is_array,
+            is_multisampled,
+            is_shadow,
+        } => {
+            let view_dimension = view_dimension(tex_type, *is_array).ok_or_else(|| {
+                WgpuLayoutError::new(path, format!("WebGPU has no {tex_type:?} texture arrays"))
+            })?;
+            let access = match resource_access {
+                ResourceAccess::Read => None,
+                ResourceAccess::Write => Some(StorageTextureAccess::WriteOnly),
+                ResourceAccess::ReadWrite | ResourceAccess::RasterOrdered => {
+                    Some(StorageTextureAccess::ReadWrite)
+                }
+                _ => {
+                    return Err(WgpuLayoutError::new(
+                        path,
+                        format!("unsupported texture access {resource_access:?}"),
+                    ));
+                }
+            };
+            match access {
+                None => BindingType::Texture {
+                    sample_type: sample_type(resource_result, *is_shadow, *is_multisampled, path)?,
+                    view_dimension,
+                    multisampled: *is_multisampled,
+                },
+                Some(access) => BindingType::StorageTexture {
+                    access,
+                    format: texture_format(*format)
+                        .or_else(|| inferred_storage_format(resource_result))
+                        .ok_or_else(|| {
+                            WgpuLayoutError::new(
+                                path,
+                                format!("storage texture format {format:?} has no wgpu format"),
+                            )
+                        })?,
+                    view_dimension,
+                },
+            }
+        }
+        BoundResource::ParameterBlock(_) | BoundResource::Array { .. } => {
+            return Err(WgpuLayoutError::new(
+                path,
+                "arrays of parameter blocks or arrays have no binding type",
+            ));
+        }
+        BoundResource::CombinedTextureSampler { .. } => {
+            return Err(WgpuLayoutError::new(
+                path,
+                "WebGPU has no combined texture-samplers",
+            ));
+        }
+        BoundResource::FeedbackTexture { .. } => {
+            return Err(WgpuLayoutError::new(
+                path,
+                "WebGPU has no feedback textures",
+            ));
+        }
+        BoundResource::TexelBuffer { .. } => {
+            return Err(WgpuLayoutError::new(path, "WebGPU has no texel buffers"));
+        }
+        BoundResource::DynamicResource => {
+            return Err(WgpuLayoutError::new(
+                path,
+                "dynamic resources have no binding type",
+            ));
+        }
+    })
+}
+
+fn uniform_buffer(size: usize) -> BindingType {
+    BindingType::Buffer {
+        ty: BufferBindingType::Uniform,
+        has_dynamic_offset: false,
+        min_binding_size: NonZeroU64::new(size as u64),
+    }
+}
+
+/// A storage buffer that must hold at least one element of `min_size` bytes.
+fn storage_buffer(read_only: bool, min_size: usize) -> BindingType {
+    BindingType::Buffer {
+        ty: BufferBindingType::Storage { read_only },
+        has_dynamic_offset: false,
+        min_binding_size: NonZeroU64::new(min_size as u64),
+    }
+}
+
+fn sample_type(
+    resource_result: &VariableReflectionType,
+    is_shadow: bool,
+    is_multisampled: bool,
+    path: &str,
+) -> Result<TextureSampleType, WgpuLayoutError> {
+    if is_shadow {
+        return Ok(TextureSampleType::Depth);
+    }
+    let scalar = match resource_result {
+        VariableReflectionType::Scalar(scalar) | VariableReflectionType::Vector(scalar, _) => {
+            *scalar
+        }
+        _ => {
+            return Err(WgpuLayoutError::new(
+                path,
+                "texture element is not a scalar or vector",
+            ));
+        }
+    };
+    Ok(match scalar {
+        ScalarType::Float32 | ScalarType::Float16 => TextureSampleType::Float {
+            // Multisampled float textures can't be filtered.
+            filterable: !is_multisampled,
+        },
+        ScalarType::Int32 => TextureSampleType::Sint,
+        ScalarType::Uint32 => TextureSampleType::Uint,
+        _ => {
+            return Err(WgpuLayoutError::new(
+                path,
+                format!("texture element type {scalar:?} can't be sampled"),
+            ));
+        }
+    })
+}
+
+/// Slang's WGSL output picks a 32-bit format matching the element type when a storage texture
+/// has no `[format(...)]`.
+fn inferred_storage_format(resource_result: &VariableReflectionType) -> Option<TextureFormat> {
+    let (scalar, count) = match resource_result {
+        VariableReflectionType::Scalar(scalar) => (*scalar, 1),
+        VariableReflectionType::Vector(scalar, count) => (*scalar, *count),
+        _ => return None,
+    };
+    Some(match (scalar, count) {
+        (ScalarType::Float32, 1) => TextureFormat::R32Float,
+        (ScalarType::Float32, 2) => TextureFormat::Rg32Float,
+        (ScalarType::Float32, 4) => TextureFormat::Rgba32Float,
+        (ScalarType::Int32, 1) => TextureFormat::R32Sint,
+        (ScalarType::Int32, 2) => TextureFormat::Rg32Sint,
+        (ScalarType::Int32, 4) => TextureFormat::Rgba32Sint,
+        (ScalarType::Uint32, 1) => TextureFormat::R32Uint,
+        (ScalarType::Uint32, 2) => TextureFormat::Rg32Uint,
+        (ScalarType::Uint32, 4) => TextureFormat::Rgba32Uint,
+        _ => return None,
+    })
+}
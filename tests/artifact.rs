@@ -0,0 +1,77 @@
+use slang_reflector::{CompileTarget, Reflector};
+
+#[test]
+fn artifact() {
+    let artifact = Reflector::builder()
+        .search_path("tests")
+        .target(CompileTarget::Wgsl)
+        .profile("spirv_1_6")
+        .target(CompileTarget::Spirv)
+        .profile("spirv_1_5")
+        .module("str_attr.slang")
+        .artifact()
+        .unwrap();
+
+    assert_eq!(artifact.targets.len(), 2);
+    for target in &artifact.targets {
+        let names = target
+            .entry_points
+            .iter()
+            .map(|e| e.name.as_str())
+            .collect::<Vec<_>>();
+        let reflected = target
+            .reflection
+            .entry_points
+            .iter()
+            .map(|e| e.name.as_str())
+            .collect::<Vec<_>>();
+        assert_eq!(names, reflected);
+        assert!(target.entry_points.iter().all(|e| !e.code.is_empty()));
+    }
+
+    let wgsl = artifact.target(CompileTarget::Wgsl).unwrap();
+    let code = wgsl.entry_points[0].as_str().unwrap();
+    assert!(code.contains("@compute"));
+
+    let spirv = artifact.target(CompileTarget::Spirv).unwrap();
+    let code = &spirv.entry_points[0].code;
+    assert_eq!(code.len() % 4, 0);
+    assert_eq!(&code[..4], &0x0723_0203u32.to_le_bytes());
+}
+
+#[cfg(feature = "derive-serde")]
+#[test]
+fn artifact_round_trip() {
+    let artifact = Reflector::builder()
+        .search_path("tests")
+        .target(CompileTarget::Spirv)
+        .profile("spirv_1_5")
+        .module("cube.slang")
+        .artifact()
+        .unwrap();
+
+    let json = serde_json::to_string(&artifact).unwrap();
+    let loaded: slang_reflector::ShaderArtifact = serde_json::from_str(&json).unwrap();
+    // Compared as values, since `hashed_strings` serializes in no particular order.
+    assert_eq!(
+        serde_json::to_value(&loaded).unwrap(),
+        serde_json::to_value(&artifact).unwrap()
+    );
+
+    let (original, loaded) = (&artifact.targets[0], &loaded.targets[0]);
+    assert_eq!(loaded.target, CompileTarget::Spirv);
+    assert_eq!(loaded.profile.as_deref(), Some("spirv_1_5"));
+    let stages = |target: &slang_reflector::TargetArtifact| {
+        target
+            .reflection
+            .entry_points
+            .iter()
+            .map(|e| (e.name.clone(), e.stage))
+            .collect::<Vec<_>>()
+    };
+    assert_eq!(stages(loaded), stages(original));
+    for (loaded, original) in loaded.entry_points.iter().zip(&original.entry_points) {
+        assert_eq!(loaded.name, original.name);
+        assert_eq!(loaded.code, original.code);
+    }
+}
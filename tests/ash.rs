@@ -0,0 +1,200 @@
+#![cfg(feature = "ash")]
+
+use ash::vk;
+use slang_reflector::ash::{descriptor_type, pipeline_layout, pipeline_layout_for};
+use slang_reflector::{BoundParameter, CompileTarget, Reflector};
+
+mod common;
+
+use common::reflect_spirv as reflect;
+
+fn descriptor_types(
+    layout: &slang_reflector::ash::PipelineLayoutReflection,
+) -> Vec<(&str, vk::DescriptorType)> {
+    layout
+        .set_layouts
+        .iter()
+        .flat_map(|set| {
+            set.names
+                .iter()
+                .zip(&set.bindings)
+                .map(|(name, binding)| (name.as_str(), binding.descriptor_type))
+        })
+        .collect()
+}
+
+#[test]
+fn descriptor_types_for_resources() {
+    // Vulkan has no feedback textures, so the layout as a whole fails.
+    let reflection = reflect("textures.slang");
+    let error = pipeline_layout(&reflection).unwrap_err();
+    assert!(error.path.starts_with("feedback"), "{error}");
+    for (name, expected) in [
+        ("layers", Some(vk::DescriptorType::SAMPLED_IMAGE)),
+        ("probes", Some(vk::DescriptorType::SAMPLED_IMAGE)),
+        ("texels", Some(vk::DescriptorType::UNIFORM_TEXEL_BUFFER)),
+        (
+            "shadowMap",
+            Some(vk::DescriptorType::COMBINED_IMAGE_SAMPLER),
+        ),
+        ("linearSampler", Some(vk::DescriptorType::SAMPLER)),
+        ("outputBuffer", Some(vk::DescriptorType::STORAGE_BUFFER)),
+        ("feedback", None),
+    ] {
+        let variable = reflection
+            .variables
+            .iter()
+            .find(|v| v.name == name)
+            .unwrap_or_else(|| panic!("Expected variable {name}"));
+        let BoundParameter::Resource { resource, .. } = &variable.reflection_type else {
+            panic!("Expected resource parameter for {name}");
+        };
+        assert_eq!(descriptor_type(resource), expected, "{name}");
+    }
+
+    let layout = pipeline_layout(&reflect("samplers.slang")).unwrap();
+    let types = descriptor_types(&layout);
+    assert!(types.contains(&("albedo", vk::DescriptorType::COMBINED_IMAGE_SAMPLER)));
+
+    let layout = pipeline_layout(&reflect("dynamic_resources.slang")).unwrap();
+    let types = descriptor_types(&layout);
+    assert!(types.contains(&("resourceHeap", vk::DescriptorType::MUTABLE_EXT)));
+
+    let layout = pipeline_layout(&reflect("buffers.slang")).unwrap();
+    let types = descriptor_types(&layout);
+    assert!(types.contains(&("input", vk::DescriptorType::STORAGE_BUFFER)));
+    assert!(types.contains(&("output", vk::DescriptorType::STORAGE_BUFFER)));
+
+    let set = &layout.set_layouts[0];
+    assert!(
+        set.bindings
+            .iter()
+            .all(|b| b.stage_flags == vk::ShaderStageFlags::COMPUTE && b.descriptor_count == 1)
+    );
+    assert!(set.bindings.is_sorted_by_key(|b| b.binding));
+}
+
+#[test]
+fn graphics_stages() {
+    let reflection = reflect("cube.slang");
+    let layout = pipeline_layout(&reflection).unwrap();
+    let types = descriptor_types(&layout);
+    assert!(types.contains(&("$Globals", vk::DescriptorType::UNIFORM_BUFFER)));
+    assert!(types.contains(&("outputTexture", vk::DescriptorType::STORAGE_IMAGE)));
+    assert!(types.contains(&("cubeTexture", vk::DescriptorType::SAMPLED_IMAGE)));
+
+    let cube_texture = layout.set_layouts[0].binding("cubeTexture").unwrap();
+    assert_eq!(
+        cube_texture.stage_flags,
+        vk::ShaderStageFlags::VERTEX | vk::ShaderStageFlags::FRAGMENT
+    );
+
+    let fragment = pipeline_layout_for(&reflection, &["fragmentMain"]).unwrap();
+    let cube_texture = fragment.set_layouts[0].binding("cubeTexture").unwrap();
+    assert_eq!(cube_texture.stage_flags, vk::ShaderStageFlags::FRAGMENT);
+}
+
+#[test]
+fn push_constant_range() {
+    let reflection = Reflector::builder()
+        .target(CompileTarget::Spirv)
+        .profile("spirv_1_5")
+        .source(
+            "push_constants",
+            r#"
+struct Params
+{
+    float4 tint;
+    float4 offset;
+};
+
+[[vk::push_constant]]
+ConstantBuffer<Params> params;
+
+RWStructuredBuffer<float4> outputBuffer;
+
+[shader("compute")]
+[numthreads(64, 1, 1)]
+void main(uint2 dispatchThreadId: SV_DispatchThreadID)
+{
+    outputBuffer[dispatchThreadId.x] = params.tint + params.offset;
+}
+"#,
+        )
+        .reflect()
+        .unwrap();
+
+    let layout = pipeline_layout(&reflection).unwrap();
+    assert_eq!(layout.push_constant_ranges.len(), 1);
+    let range = layout.push_constant_ranges[0];
+    assert_eq!(range.offset, 0);
+    assert_eq!(range.size, 32);
+    assert_eq!(range.stage_flags, vk::ShaderStageFlags::COMPUTE);
+
+    let types = descriptor_types(&layout);
+    assert_eq!(
+        types,
+        [("outputBuffer", vk::DescriptorType::STORAGE_BUFFER)]
+    );
+}
+
+#[test]
+fn entry_point_uniforms_are_push_constants() {
+    let layout = pipeline_layout(&reflect("entry_point_uniforms.slang")).unwrap();
+    assert_eq!(layout.push_constant_ranges.len(), 1);
+    let range = layout.push_constant_ranges[0];
+    assert_eq!(range.offset, 0);
+    assert!(range.size >= 20);
+    assert_eq!(range.stage_flags, vk::ShaderStageFlags::COMPUTE);
+    assert_eq!(
+        descriptor_types(&layout),
+        [("outputBuffer", vk::DescriptorType::STORAGE_BUFFER)]
+    );
+}
+
+#[test]
+fn push_constant_ranges_per_stage() {
+    // The global block (32 bytes) is visible to both stages, the fragment shader's uniform
+    // parameters (64 bytes) only to the fragment stage.
+    let layout = pipeline_layout(&reflect("push_constant_stages.slang")).unwrap();
+    let mut ranges = layout
+        .push_constant_ranges
+        .iter()
+        .map(|r| (r.stage_flags, r.offset, r.size))
+        .collect::<Vec<_>>();
+    ranges.sort_by_key(|&(_, _, size)| size);
+    assert_eq!(
+        ranges,
+        [
+            (vk::ShaderStageFlags::VERTEX, 0, 32),
+            (vk::ShaderStageFlags::FRAGMENT, 0, 64),
+        ]
+    );
+}
+
+#[test]
+fn parameter_block_set() {
+    let layout = pipeline_layout(&reflect("parameter_block.slang")).unwrap();
+    let set = layout
+        .set_layouts
+        .iter()
+        .find(|set| set.names.iter().any(|name| name == "material"))
+        .expect("Expected a set for the parameter block");
+    assert_ne!(set.set, 0, "Parameter block should get a set of its own");
+
+    let bindings = set
+        .names
+        .iter()
+        .zip(&set.bindings)
+        .map(|(name, b)| (name.as_str(), b.binding, b.descriptor_type))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        bindings,
+        [
+            ("material", 0, vk::DescriptorType::UNIFORM_BUFFER),
+            ("material.albedo", 1, vk::DescriptorType::SAMPLED_IMAGE),
+            ("material.albedoSampler", 2, vk::DescriptorType::SAMPLER),
+        ]
+    );
+    assert_eq!(set.binding("material").unwrap().descriptor_count, 1);
+}
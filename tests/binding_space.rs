@@ -0,0 +1,35 @@
+use slang_reflector::{BoundParameter, CompileTarget, ParameterCategory, Reflector};
+
+#[test]
+fn binding_space() {
+    let reflection = Reflector::builder()
+        .search_path("tests")
+        .target(CompileTarget::Wgsl)
+        .profile("spirv_1_6")
+        .module("binding_space.slang")
+        .reflect()
+        .unwrap();
+    let bindings = reflection
+        .variables
+        .iter()
+        .map(|v| {
+            let BoundParameter::Resource {
+                binding_index,
+                binding_space,
+                category,
+                ..
+            } = v.reflection_type
+            else {
+                panic!("Expected resource parameter for {}", v.name);
+            };
+            (v.name.as_str(), binding_space, binding_index, category)
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(
+        bindings,
+        [
+            ("albedo", 2, 3, ParameterCategory::DescriptorTableSlot),
+            ("outputBuffer", 1, 0, ParameterCategory::DescriptorTableSlot)
+        ]
+    );
+}
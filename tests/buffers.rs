@@ -0,0 +1,73 @@
+use slang_reflector::{
+    BoundParameter, BoundResource, CompileTarget, CounterBinding, Reflector, ResourceAccess,
+};
+
+#[test]
+fn buffers() {
+    let reflection = Reflector::builder()
+        .search_path("tests")
+        .target(CompileTarget::Wgsl)
+        .profile("spirv_1_6")
+        .module("buffers.slang")
+        .reflect()
+        .unwrap();
+    let resource = |name: &str| {
+        let variable = reflection
+            .variables
+            .iter()
+            .find(|v| v.name == name)
+            .unwrap_or_else(|| panic!("Expected variable {name}"));
+        let BoundParameter::Resource { resource, .. } = &variable.reflection_type else {
+            panic!("Expected resource parameter for {name}");
+        };
+        resource
+    };
+
+    assert!(matches!(
+        resource("input"),
+        BoundResource::RawBuffer {
+            resource_access: ResourceAccess::Read
+        }
+    ));
+    assert!(matches!(
+        resource("output"),
+        BoundResource::RawBuffer {
+            resource_access: ResourceAccess::ReadWrite
+        }
+    ));
+    let Some(BoundParameter::Resource {
+        resource:
+            BoundResource::AppendConsumeBuffer {
+                resource_access,
+                counter,
+                ..
+            },
+        binding_index,
+        binding_space,
+        ..
+    }) = reflection
+        .variables
+        .iter()
+        .find(|v| v.name == "visibleInstances")
+        .map(|v| &v.reflection_type)
+    else {
+        panic!("Expected append buffer");
+    };
+    assert_eq!((*binding_index, *binding_space), (2, 0));
+    assert_eq!(*resource_access, ResourceAccess::Append);
+    // The counter takes the slot right after its buffer.
+    assert_eq!(
+        *counter,
+        Some(CounterBinding {
+            binding_index: 3,
+            binding_space: 0,
+        })
+    );
+    assert!(matches!(
+        resource("pendingInstances"),
+        BoundResource::AppendConsumeBuffer {
+            resource_access: ResourceAccess::Consume,
+            ..
+        }
+    ));
+}
@@ -0,0 +1,120 @@
+#![cfg(feature = "build")]
+
+use slang_reflector::{CompileTarget, Reflector, build::Build};
+
+#[test]
+fn build() {
+    let out_dir = std::env::temp_dir().join("slang-reflector-build-test");
+    let outputs = Build::new(
+        Reflector::builder()
+            .target(CompileTarget::Wgsl)
+            .profile("spirv_1_6"),
+    )
+    .file("tests/multi_entrypoint.slang")
+    .out_dir(&out_dir)
+    .cargo_metadata(false)
+    .try_compile()
+    .unwrap();
+
+    assert_eq!(outputs.len(), 1);
+    let output = &outputs[0];
+    assert!(output.dependency_files.len() > 1);
+    assert!(
+        output
+            .dependency_files
+            .iter()
+            .any(|file| file.ends_with("multi_entrypoint2.slang"))
+    );
+
+    assert!(out_dir.join("tests_multi_entrypoint.rs").is_file());
+    assert!(out_dir.join("tests_multi_entrypoint.json").is_file());
+    let target = &output.artifact.targets[0];
+    for entry_point in &target.entry_points {
+        let path = out_dir.join(format!("tests_multi_entrypoint.{}.wgsl", entry_point.name));
+        assert_eq!(std::fs::read(&path).unwrap(), entry_point.code);
+    }
+}
+
+#[test]
+fn build_loads_file_by_path() {
+    // `tests` also has a `cube.slang`, which must not be picked up instead.
+    let dir = std::env::temp_dir().join("slang-reflector-build-path-test");
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(
+        dir.join("cube.slang"),
+        "RWStructuredBuffer<float> output;\n\n\
+         [shader(\"compute\")]\n[numthreads(1, 1, 1)]\n\
+         void shadowingMain() { output[0] = 1.0; }\n",
+    )
+    .unwrap();
+
+    let outputs = Build::new(
+        Reflector::builder()
+            .search_path("tests")
+            .target(CompileTarget::Wgsl)
+            .profile("spirv_1_6"),
+    )
+    .file(dir.join("cube.slang"))
+    .out_dir(dir.join("out"))
+    .cargo_metadata(false)
+    .try_compile()
+    .unwrap();
+
+    let entry_points = &outputs[0].artifact.targets[0].reflection.entry_points;
+    assert_eq!(entry_points.len(), 1);
+    assert_eq!(entry_points[0].name, "shadowingMain");
+}
+
+#[test]
+fn build_files_of_the_same_name() {
+    // Each `main.slang` imports the `common.slang` next to it, not the other one.
+    let dir = std::env::temp_dir().join("slang-reflector-build-same-name-test");
+    for (subdir, value) in [("first", "1.0"), ("second", "2.0")] {
+        std::fs::create_dir_all(dir.join(subdir)).unwrap();
+        std::fs::write(
+            dir.join(subdir).join("common.slang"),
+            format!("float value() {{ return {value}; }}\n"),
+        )
+        .unwrap();
+        std::fs::write(
+            dir.join(subdir).join("main.slang"),
+            format!(
+                "import common;\n\nRWStructuredBuffer<float> output;\n\n\
+                 [shader(\"compute\")]\n[numthreads(1, 1, 1)]\n\
+                 void {subdir}Main() {{ output[0] = value(); }}\n"
+            ),
+        )
+        .unwrap();
+    }
+
+    let out_dir = dir.join("out");
+    let outputs = Build::new(
+        Reflector::builder()
+            .target(CompileTarget::Wgsl)
+            .profile("spirv_1_6"),
+    )
+    .file(dir.join("first/main.slang"))
+    .file(dir.join("second/main.slang"))
+    .out_dir(&out_dir)
+    .cargo_metadata(false)
+    .try_compile()
+    .unwrap();
+
+    assert_eq!(outputs.len(), 2);
+    assert_ne!(outputs[0].outputs, outputs[1].outputs);
+    for (output, subdir) in outputs.iter().zip(["first", "second"]) {
+        assert!(
+            output
+                .dependency_files
+                .iter()
+                .any(|file| file.ends_with(format!("{subdir}/common.slang")))
+        );
+        let rust_file = output
+            .outputs
+            .iter()
+            .find(|path| path.extension().is_some_and(|extension| extension == "rs"));
+        let rust_file = rust_file.unwrap().file_name().unwrap().to_string_lossy();
+        assert!(rust_file.ends_with(&format!("{subdir}_main.rs")));
+        assert!(out_dir.join(&*rust_file).is_file());
+    }
+}
@@ -0,0 +1,102 @@
+use slang_reflector::RustCodegen;
+
+mod common;
+
+use common::reflect;
+
+#[test]
+fn padded_struct() {
+    let source = RustCodegen::new()
+        .bytemuck(true)
+        .program(&reflect("layout.slang"))
+        .generate()
+        .unwrap();
+
+    assert_eq!(
+        source,
+        "\
+#[repr(C)]
+#[derive(Debug, Clone, Copy, PartialEq, bytemuck::Pod, bytemuck::Zeroable)]
+pub struct Particle {
+    pub position: [f32; 3],
+    pub radius: f32,
+    pub velocity: [f32; 2],
+    pub alive: u32,
+    pub weights: [f32; 3],
+    pub _pad0: [u8; 8],
+}
+
+const _: () = assert!(::core::mem::size_of::<Particle>() == 48);
+const _: () = assert!(::core::mem::offset_of!(Particle, position) == 0);
+const _: () = assert!(::core::mem::offset_of!(Particle, radius) == 12);
+const _: () = assert!(::core::mem::offset_of!(Particle, velocity) == 16);
+const _: () = assert!(::core::mem::offset_of!(Particle, alive) == 24);
+const _: () = assert!(::core::mem::offset_of!(Particle, weights) == 28);
+"
+    );
+}
+
+#[test]
+fn matrix_struct() {
+    let source = RustCodegen::new()
+        .layout_assertions(false)
+        .program(&reflect("matrix.slang"))
+        .generate()
+        .unwrap();
+
+    assert!(source.contains("pub struct Camera {"));
+    assert!(source.contains("    pub view: [[f32; 4]; 4],\n"));
+    assert!(source.contains("    pub normal: [[f32; 4]; 3],\n"));
+    assert!(!source.contains("const _"));
+}
+
+#[test]
+fn annotated_struct() {
+    let source = RustCodegen::new()
+        .program(&reflect("str_attr.slang"))
+        .generate()
+        .unwrap();
+
+    assert!(source.starts_with(
+        "#[doc = \"This is a test annotation for the Rust struct generated from Slang sdf.\"]\n\
+         #[repr(C)]\n"
+    ));
+    assert!(source.contains("    pub prop1: u32,\n"));
+}
+
+#[test]
+fn constant_buffer_struct() {
+    let source = RustCodegen::new()
+        .layout_assertions(false)
+        .program(&reflect("constant_buffer.slang"))
+        .generate()
+        .unwrap();
+
+    assert!(source.contains("pub struct Params {"));
+    assert!(source.contains("    pub tint: [f32; 4],\n    pub scale: f32,\n"));
+}
+
+#[test]
+fn push_constant_struct() {
+    let source = RustCodegen::new()
+        .layout_assertions(false)
+        .program(&common::reflect_spirv("push_constants.slang"))
+        .generate()
+        .unwrap();
+
+    assert!(source.contains("pub struct Params {"));
+    assert!(source.contains("    pub tint: [f32; 4],\n    pub offset: [f32; 4],\n"));
+}
+
+#[test]
+fn parameter_block_struct() {
+    let source = RustCodegen::new()
+        .layout_assertions(false)
+        .program(&reflect("parameter_block.slang"))
+        .generate()
+        .unwrap();
+
+    assert!(source.contains("pub struct Material {"));
+    assert!(source.contains("    pub roughness: f32,\n"));
+    assert!(!source.contains("albedo"));
+}
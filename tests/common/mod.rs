@@ -0,0 +1,24 @@
+//! Helpers shared by the integration tests. Each test crate uses only some of them.
+#![allow(dead_code)]
+
+use slang_reflector::{CompileTarget, ProgramReflection, Reflector};
+
+/// Reflects `module` from `tests` for WGSL.
+pub fn reflect(module: &str) -> ProgramReflection {
+    reflect_target(module, CompileTarget::Wgsl, "spirv_1_6")
+}
+
+/// Reflects `module` from `tests` for SPIR-V.
+pub fn reflect_spirv(module: &str) -> ProgramReflection {
+    reflect_target(module, CompileTarget::Spirv, "spirv_1_5")
+}
+
+pub fn reflect_target(module: &str, target: CompileTarget, profile: &str) -> ProgramReflection {
+    Reflector::builder()
+        .search_path("tests")
+        .target(target)
+        .profile(profile)
+        .module(module)
+        .reflect()
+        .unwrap()
+}
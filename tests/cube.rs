@@ -1,65 +1,44 @@
-use slang_reflector::{ProgramLayoutReflector, Downcast};
+use slang_reflector::{CompileTarget, Reflector, Stage};
 
 #[test]
 fn cube() {
-    let global_slang_session = slang_reflector::GlobalSession::new().unwrap();
-
-    let session_options = slang_reflector::CompilerOptions::default()
-        .optimization(slang_reflector::OptimizationLevel::High)
-        .matrix_layout_row(true);
-
-    let target_desc = slang_reflector::TargetDesc::default()
-        .format(slang_reflector::CompileTarget::Wgsl)
-        .profile(global_slang_session.find_profile("spirv_1_6"));
-
-    let targets = [target_desc];
-
-    let search_paths = vec!["tests"];
-
-    let search_paths = search_paths
-        .into_iter()
-        .map(std::ffi::CString::new)
-        .map(Result::unwrap)
-        .collect::<Vec<_>>();
-    let search_paths = search_paths.iter().map(|p| p.as_ptr()).collect::<Vec<_>>();
-
-    let session_desc = slang_reflector::SessionDesc::default()
-        .search_paths(&search_paths)
-        .targets(&targets)
-        .options(&session_options);
-
-    let Some(slang_session) = global_slang_session.create_session(&session_desc) else {
-        panic!("Failed to create slang session");
-    };
-
-    println!("Session loaded");
-
-    let mut components: Vec<slang_reflector::ComponentType> = vec![];
-    {
-        let module = slang_session
-            .load_module(&"cube.slang")
-            .unwrap_or_else(|e| {
-                panic!(
-                    "Failed to load module: {:?}",
-                    e.to_string()
-                )
-            });
-
-        println!("Module loaded");
-
-        components.push(module.downcast().clone());
-
-        for entry_point in module.entry_points() {
-            components.push(entry_point.downcast().clone());
-        }
-    }
-
-    let program = slang_session
-        .create_composite_component_type(components.as_slice())
+    let reflection = Reflector::builder()
+        .search_path("tests")
+        .target(CompileTarget::Wgsl)
+        .profile("spirv_1_6")
+        .module("cube.slang")
+        .reflect()
         .unwrap();
-    let linked_program = program.link().unwrap();
-
-    let shader_reflection = linked_program.layout(0).unwrap();
 
-    shader_reflection.reflect();
-}
\ No newline at end of file
+    let vertex = reflection
+        .entry_points
+        .iter()
+        .find(|e| e.name == "vertexMain")
+        .expect("Expected vertexMain entry point");
+    assert_eq!(vertex.stage, Stage::Vertex);
+    assert_eq!(vertex.thread_group_size, None);
+    assert_eq!(vertex.inputs.len(), 1);
+    assert_eq!(vertex.inputs[0].name, "vertexID");
+    assert_eq!(
+        vertex.inputs[0].semantic_name.as_deref(),
+        Some("SV_VertexID")
+    );
+    let position = vertex
+        .outputs
+        .iter()
+        .find(|o| o.name == "result.position")
+        .expect("Expected position output");
+    assert_eq!(position.semantic_name.as_deref(), Some("SV_Position"));
+
+    let fragment = reflection
+        .entry_points
+        .iter()
+        .find(|e| e.name == "fragmentMain")
+        .expect("Expected fragmentMain entry point");
+    assert_eq!(fragment.stage, Stage::Fragment);
+    assert_eq!(fragment.outputs.len(), 1);
+    assert_eq!(
+        fragment.outputs[0].semantic_name.as_deref(),
+        Some("SV_Target")
+    );
+}
@@ -0,0 +1,125 @@
+use slang_reflector::{CompileTarget, Diagnostic, Reflector, ReflectorError, Severity};
+
+#[test]
+fn load_module_diagnostics() {
+    let error = Reflector::builder()
+        .target(CompileTarget::Wgsl)
+        .profile("spirv_1_6")
+        .source(
+            "broken",
+            "[shader(\"compute\")]\n[numthreads(1, 1, 1)]\nvoid main()\n{\n    float x = missing;\n}\n",
+        )
+        .reflect()
+        .unwrap_err();
+
+    assert!(matches!(error, ReflectorError::LoadModule { .. }));
+    let diagnostic = error
+        .diagnostics()
+        .iter()
+        .find(|d| d.is_error())
+        .expect("an error diagnostic");
+    assert_eq!(diagnostic.severity, Severity::Error);
+    assert_eq!(diagnostic.file.as_deref(), Some("broken.slang"));
+    assert_eq!(diagnostic.line, Some(5));
+    assert_eq!(diagnostic.column, Some(15));
+    assert_eq!(diagnostic.code, Some(30015));
+    assert!(diagnostic.message.contains("missing"));
+}
+
+#[test]
+fn warnings_of_successful_compile() {
+    let program = Reflector::builder()
+        .target(CompileTarget::Wgsl)
+        .profile("spirv_1_6")
+        .source(
+            "truncating",
+            "RWStructuredBuffer<int> output;\n\n[shader(\"compute\")]\n[numthreads(1, 1, 1)]\nvoid main()\n{\n    int x = 1.5;\n    output[0] = x;\n}\n",
+        )
+        .compile()
+        .unwrap();
+
+    let warning = program
+        .diagnostics()
+        .iter()
+        .find(|d| d.severity == Severity::Warning)
+        .expect("a warning diagnostic");
+    assert_eq!(warning.file.as_deref(), Some("truncating.slang"));
+    assert_eq!(warning.line, Some(7));
+    assert!(program.diagnostics().iter().all(|d| !d.is_error()));
+}
+
+#[test]
+fn parse_diagnostics() {
+    let text = "\
+shaders/cube.slang(12): warning 30081: implicit conversion from 'int' to 'float'.
+    float y = 1;
+              ^
+shaders/cube.slang(20,5): error 30015: undefined identifier 'foo'.
+(0): note: see declaration
+";
+    let diagnostics = Diagnostic::parse(text);
+    assert_eq!(
+        diagnostics,
+        [
+            Diagnostic {
+                severity: Severity::Warning,
+                file: Some("shaders/cube.slang".to_string()),
+                line: Some(12),
+                column: Some(15),
+                code: Some(30081),
+                message: "implicit conversion from 'int' to 'float'.".to_string(),
+            },
+            Diagnostic {
+                severity: Severity::Error,
+                file: Some("shaders/cube.slang".to_string()),
+                line: Some(20),
+                column: Some(5),
+                code: Some(30015),
+                message: "undefined identifier 'foo'.".to_string(),
+            },
+            Diagnostic {
+                severity: Severity::Note,
+                file: None,
+                line: None,
+                column: None,
+                code: None,
+                message: "see declaration".to_string(),
+            },
+        ]
+    );
+
+    let unstructured = Diagnostic::parse("something went wrong");
+    assert_eq!(unstructured.len(), 1);
+    assert_eq!(unstructured[0].severity, Severity::Error);
+    assert_eq!(unstructured[0].message, "something went wrong");
+}
+
+#[test]
+fn parse_diagnostic_headers_with_parentheses() {
+    let text = "\
+shaders (copy)/cube.slang(3): error 30019: expected 'float' (got 'int'): see foo(1): error 2: x
+shaders/cube.slang(line 4): warning 30081: implicit conversion.
+";
+    let diagnostics = Diagnostic::parse(text);
+    assert_eq!(
+        diagnostics,
+        [
+            Diagnostic {
+                severity: Severity::Error,
+                file: Some("shaders (copy)/cube.slang".to_string()),
+                line: Some(3),
+                column: None,
+                code: Some(30019),
+                message: "expected 'float' (got 'int'): see foo(1): error 2: x".to_string(),
+            },
+            Diagnostic {
+                severity: Severity::Warning,
+                file: None,
+                line: None,
+                column: None,
+                code: Some(30081),
+                message: "implicit conversion.".to_string(),
+            },
+        ]
+    );
+}
@@ -0,0 +1,61 @@
+use slang_reflector::{BoundParameter, CompileTarget, EntryPointUniformBuffer, Reflector};
+
+#[test]
+fn entry_point_uniform_buffer() {
+    let targets = Reflector::builder()
+        .search_path("tests")
+        .target(CompileTarget::Wgsl)
+        .profile("spirv_1_6")
+        .target(CompileTarget::Spirv)
+        .profile("spirv_1_5")
+        .module("entry_point_uniforms.slang")
+        .reflect_targets()
+        .unwrap();
+
+    let wgsl = &targets[0].reflection.entry_points[0];
+    let uniforms = wgsl
+        .uniforms
+        .iter()
+        .map(|u| u.name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(uniforms, ["tint", "scale"]);
+    assert!(matches!(
+        wgsl.uniforms[1].reflection_type,
+        BoundParameter::Uniform {
+            uniform_offset: 16,
+            ..
+        }
+    ));
+    let Some(EntryPointUniformBuffer::ConstantBuffer {
+        binding_space,
+        size,
+        ..
+    }) = wgsl.uniform_buffer
+    else {
+        panic!("Expected a uniform buffer on WGSL");
+    };
+    assert_eq!(binding_space, 0);
+    // `float4` and `float`, rounded up to the struct's 16-byte alignment.
+    assert_eq!(size, 32);
+
+    // Vulkan puts entry point uniforms in push constants.
+    let spirv = &targets[1].reflection.entry_points[0];
+    assert!(matches!(
+        spirv.uniform_buffer,
+        Some(EntryPointUniformBuffer::PushConstants { size: 32 })
+    ));
+
+    let without_uniforms = Reflector::builder()
+        .search_path("tests")
+        .target(CompileTarget::Wgsl)
+        .profile("spirv_1_6")
+        .module("buffers.slang")
+        .reflect()
+        .unwrap();
+    assert!(
+        without_uniforms
+            .entry_points
+            .iter()
+            .all(|e| e.uniform_buffer.is_none())
+    );
+}
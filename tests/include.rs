@@ -0,0 +1,23 @@
+use slang_reflector::{CompileTarget, Reflector};
+
+#[test]
+fn included_slang_file_is_not_a_module() {
+    let compiled_program = Reflector::builder()
+        .search_path("tests")
+        .target(CompileTarget::Wgsl)
+        .module("include.slang")
+        .compile()
+        .unwrap();
+
+    let reflection = compiled_program.reflect().unwrap();
+    assert_eq!(reflection.entry_points.len(), 1);
+    assert_eq!(reflection.entry_points[0].name, "main");
+
+    assert!(
+        compiled_program
+            .dependency_files()
+            .iter()
+            .any(|file| file.ends_with("include_lighting.slang")),
+        "Missing dependency include_lighting.slang"
+    );
+}
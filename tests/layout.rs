@@ -0,0 +1,60 @@
+use slang_reflector::{
+    BoundParameter, BoundResource, CompileTarget, Reflector, VariableReflectionType,
+};
+
+#[test]
+fn layout() {
+    let reflection = Reflector::builder()
+        .search_path("tests")
+        .target(CompileTarget::Wgsl)
+        .profile("spirv_1_6")
+        .module("layout.slang")
+        .reflect()
+        .unwrap();
+    let particles = &reflection.variables[0];
+    let BoundParameter::Resource {
+        resource:
+            BoundResource::StructuredBuffer {
+                resource_result,
+                element_layout,
+                ..
+            },
+        ..
+    } = &particles.reflection_type
+    else {
+        panic!("Expected structured buffer resource");
+    };
+    let VariableReflectionType::Struct { props, layout, .. } = resource_result else {
+        panic!("Expected struct type for particle");
+    };
+    assert_eq!(layout.size, 40);
+    assert_eq!(element_layout.stride, 48);
+
+    let offsets = props
+        .iter()
+        .map(|field| (field.name.as_str(), field.offset))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        offsets,
+        [
+            ("position", 0),
+            ("radius", 12),
+            ("velocity", 16),
+            ("alive", 24),
+            ("weights", 28)
+        ]
+    );
+
+    let weights = &props[4];
+    let VariableReflectionType::Array {
+        count,
+        element_layout,
+        ..
+    } = &weights.reflection_type
+    else {
+        panic!("Expected array type for weights");
+    };
+    assert_eq!(*count, 3);
+    assert_eq!(element_layout.stride, 4);
+    assert_eq!(weights.layout.size, 12);
+}
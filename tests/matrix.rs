@@ -0,0 +1,58 @@
+use slang_reflector::{
+    BoundParameter, CompileTarget, CompilerOptions, MatrixLayout, OptimizationLevel, Reflector,
+    VariableReflectionType,
+};
+
+#[test]
+fn matrix() {
+    let reflection = Reflector::builder()
+        .search_path("tests")
+        .options(
+            CompilerOptions::default()
+                .optimization(OptimizationLevel::High)
+                .matrix_layout_row(true),
+        )
+        .target(CompileTarget::Wgsl)
+        .profile("spirv_1_6")
+        .module("matrix.slang")
+        .reflect()
+        .unwrap();
+    let camera = reflection
+        .variables
+        .iter()
+        .find(|v| v.name == "camera")
+        .expect("Expected camera uniform");
+    let BoundParameter::Uniform {
+        resource_result, ..
+    } = &camera.reflection_type
+    else {
+        panic!("Expected uniform parameter");
+    };
+    let VariableReflectionType::Struct { props, .. } = resource_result else {
+        panic!("Expected struct type for camera");
+    };
+    let view = &props[0];
+    assert_eq!(view.name, "view");
+    let VariableReflectionType::Matrix {
+        rows,
+        columns,
+        layout,
+        ..
+    } = view.reflection_type
+    else {
+        panic!("Expected matrix type for view");
+    };
+    assert_eq!((rows, columns), (4, 4));
+    assert_eq!(layout, MatrixLayout::RowMajor);
+    assert_eq!(view.offset, 0);
+    assert_eq!(view.layout.size, 64);
+
+    let normal = &props[1];
+    assert_eq!(normal.name, "normal");
+    let VariableReflectionType::Matrix { stride, .. } = normal.reflection_type else {
+        panic!("Expected matrix type for normal");
+    };
+    assert_eq!(stride, 16);
+    assert_eq!(normal.offset, 64);
+    assert_eq!(normal.layout.size, 48);
+}
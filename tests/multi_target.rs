@@ -0,0 +1,66 @@
+use slang_reflector::{BoundParameter, CompileTarget, Reflector};
+
+#[test]
+fn multi_target() {
+    let target_reflections = Reflector::builder()
+        .search_path("tests")
+        .target(CompileTarget::Wgsl)
+        .profile("spirv_1_6")
+        .target(CompileTarget::Spirv)
+        .profile("spirv_1_5")
+        .target(CompileTarget::Hlsl)
+        .profile("sm_6_0")
+        .module("str_attr.slang")
+        .reflect_targets()
+        .unwrap();
+
+    let targets = target_reflections
+        .iter()
+        .map(|t| (t.target, t.profile.as_deref()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        targets,
+        [
+            (CompileTarget::Wgsl, Some("spirv_1_6")),
+            (CompileTarget::Spirv, Some("spirv_1_5")),
+            (CompileTarget::Hlsl, Some("sm_6_0"))
+        ]
+    );
+
+    for target_reflection in &target_reflections {
+        let reflection = &target_reflection.reflection;
+        assert_eq!(reflection.variables.len(), 1);
+        assert_eq!(reflection.variables[0].name, "outputBuffer");
+        assert!(matches!(
+            reflection.variables[0].reflection_type,
+            BoundParameter::Resource {
+                binding_index: 0,
+                ..
+            }
+        ));
+    }
+}
+
+#[test]
+fn profile_before_target() {
+    let target_reflections = Reflector::builder()
+        .search_path("tests")
+        .profile("spirv_1_5")
+        .target(CompileTarget::Spirv)
+        .target(CompileTarget::Wgsl)
+        .module("str_attr.slang")
+        .reflect_targets()
+        .unwrap();
+
+    let targets = target_reflections
+        .iter()
+        .map(|t| (t.target, t.profile.as_deref()))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        targets,
+        [
+            (CompileTarget::Spirv, Some("spirv_1_5")),
+            (CompileTarget::Wgsl, None)
+        ]
+    );
+}
@@ -0,0 +1,71 @@
+use slang_reflector::{BoundParameter, BoundResource, CompileTarget, Reflector};
+
+#[test]
+fn parameter_block() {
+    let reflection = Reflector::builder()
+        .search_path("tests")
+        .target(CompileTarget::Wgsl)
+        .profile("spirv_1_6")
+        .module("parameter_block.slang")
+        .reflect()
+        .unwrap();
+    let material = reflection
+        .variables
+        .iter()
+        .find(|v| v.name == "material")
+        .expect("Expected material parameter block");
+    let BoundParameter::Resource {
+        resource: BoundResource::ParameterBlock(block),
+        ..
+    } = &material.reflection_type
+    else {
+        panic!("Expected parameter block resource");
+    };
+    // `float4` and `float`, padded to the alignment of `float4`.
+    assert_eq!(block.uniform_size, 32);
+    let material_space = block.space;
+    let names = block
+        .variables
+        .iter()
+        .map(|v| v.name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(names, ["baseColor", "roughness", "albedo", "albedoSampler"]);
+    assert!(matches!(
+        block.variables[2].reflection_type,
+        BoundParameter::Resource {
+            resource: BoundResource::Texture { .. },
+            ..
+        }
+    ));
+    // The uniform data of the block is at binding 0 of its group, its resources follow.
+    let bindings = block.variables[2..]
+        .iter()
+        .map(|v| match v.reflection_type {
+            BoundParameter::Resource {
+                binding_index,
+                binding_space,
+                ..
+            } => (binding_index, binding_space),
+            _ => panic!("Expected resource parameter for {}", v.name),
+        })
+        .collect::<Vec<_>>();
+    assert_eq!(bindings, [(1, 0), (2, 0)]);
+
+    let lighting = reflection
+        .variables
+        .iter()
+        .find(|v| v.name == "lighting")
+        .expect("Expected lighting constant buffer");
+    let BoundParameter::Resource {
+        resource: BoundResource::ConstantBuffer(block),
+        ..
+    } = &lighting.reflection_type
+    else {
+        panic!("Expected constant buffer resource");
+    };
+    assert_eq!(block.uniform_size, 16);
+    assert_ne!(
+        block.space, material_space,
+        "Parameter block should get a space of its own"
+    );
+}
@@ -0,0 +1,374 @@
+use slang_reflector::playground::{
+    self, BufferContents, Dispatch, DispatchFrequency, DispatchSize, PlaygroundAttribute,
+    TextureAlias, TextureSource, UniformSource,
+};
+use slang_reflector::{TextureType, UserAttributeParameter, UserAttributeReflection};
+
+mod common;
+
+use common::reflect;
+
+#[test]
+fn playground_attributes() {
+    let reflection = reflect("playground_attributes.slang");
+    let attributes = playground::attributes(&reflection);
+    assert!(attributes.diagnostics.is_empty());
+
+    let expected = [
+        ("time", PlaygroundAttribute::Time),
+        ("deltaTime", PlaygroundAttribute::DeltaTime),
+        ("mousePosition", PlaygroundAttribute::MousePosition),
+        (
+            "spacePressed",
+            PlaygroundAttribute::KeyInput("Space".to_string()),
+        ),
+        (
+            "brightness",
+            PlaygroundAttribute::Slider {
+                default: 0.5,
+                min: 0.0,
+                max: 1.0,
+            },
+        ),
+        (
+            "tint",
+            PlaygroundAttribute::ColorPick {
+                default: [1.0, 0.5, 0.25],
+            },
+        ),
+        ("zeros", PlaygroundAttribute::Zeros { count: 1024 }),
+        ("noise", PlaygroundAttribute::Rand { count: 131072 }),
+        (
+            "canvas",
+            PlaygroundAttribute::Black {
+                width: 256,
+                height: 128,
+            },
+        ),
+        ("volume", PlaygroundAttribute::Black3d { size: [16, 16, 8] }),
+        (
+            "outputTexture",
+            PlaygroundAttribute::BlackScreen {
+                width_scale: 1.0,
+                height_scale: 0.5,
+            },
+        ),
+        (
+            "drawTexture",
+            PlaygroundAttribute::RebindForDraw("outputTexture".to_string()),
+        ),
+        (
+            "jeep",
+            PlaygroundAttribute::Url("static/jeep.jpg".to_string()),
+        ),
+        ("linearSampler", PlaygroundAttribute::Sampler),
+    ];
+    for (name, attribute) in expected {
+        assert_eq!(attributes.variable(name), [attribute], "{name}");
+    }
+    assert!(attributes.variable("scratch").is_empty());
+    assert_eq!(attributes.variables.len(), 14);
+
+    assert_eq!(
+        attributes.entry_point("render"),
+        [PlaygroundAttribute::Call {
+            threads: [256, 128, 1]
+        }]
+    );
+    assert_eq!(
+        attributes.entry_point("fillNoise"),
+        [
+            PlaygroundAttribute::CallSizeOf("noise".to_string()),
+            PlaygroundAttribute::CallOnce,
+        ]
+    );
+    assert_eq!(
+        attributes.entry_point("vertexMain"),
+        [PlaygroundAttribute::Draw {
+            vertex_count: 3,
+            fragment_entry_point: "fragmentMain".to_string(),
+        }]
+    );
+    assert!(attributes.entry_point("fragmentMain").is_empty());
+}
+
+#[test]
+fn invalid_arguments() {
+    let attribute = |name: &str, parameters| UserAttributeReflection {
+        name: name.to_string(),
+        parameters,
+    };
+
+    assert_eq!(
+        PlaygroundAttribute::from_user_attribute(&attribute("maybe_unused", vec![])),
+        Ok(None)
+    );
+    assert_eq!(
+        PlaygroundAttribute::from_user_attribute(&attribute(
+            "playground_BLACK_SCREEN",
+            vec![
+                UserAttributeParameter::Int(1),
+                UserAttributeParameter::Int(2)
+            ],
+        )),
+        Ok(Some(PlaygroundAttribute::BlackScreen {
+            width_scale: 1.0,
+            height_scale: 2.0,
+        }))
+    );
+    assert_eq!(
+        PlaygroundAttribute::from_user_attribute(&attribute(
+            "playground_RAND",
+            vec![
+                UserAttributeParameter::Int(1),
+                UserAttributeParameter::Int(2)
+            ],
+        )),
+        Err("`playground::RAND` expects 1 argument, found 2".to_string())
+    );
+    assert_eq!(
+        PlaygroundAttribute::from_user_attribute(&attribute(
+            "playground_CALL_SIZE_OF",
+            vec![UserAttributeParameter::Int(4)],
+        )),
+        Err("argument 1 of `playground::CALL::SIZE_OF` must be a string, found 4".to_string())
+    );
+    assert_eq!(
+        PlaygroundAttribute::from_user_attribute(&attribute(
+            "playground_BLACK",
+            vec![
+                UserAttributeParameter::Int(64),
+                UserAttributeParameter::Int(-1)
+            ],
+        )),
+        Err(
+            "argument 2 of `playground::BLACK` must be a non-negative integer, found -1"
+                .to_string()
+        )
+    );
+    assert_eq!(
+        PlaygroundAttribute::from_user_attribute(&attribute("playground_SPARKLE", vec![])),
+        Err("unknown attribute `playground::SPARKLE`".to_string())
+    );
+}
+
+#[test]
+fn diagnostics_name_the_variable() {
+    let mut reflection = reflect("multi_entrypoint.slang");
+    let buf = reflection
+        .variables
+        .iter_mut()
+        .find(|v| v.name == "buf")
+        .unwrap();
+    buf.user_attributes[0].parameters.clear();
+
+    let attributes = playground::attributes(&reflection);
+    assert!(attributes.variable("buf").is_empty());
+    assert_eq!(attributes.variable("time"), [PlaygroundAttribute::Time]);
+    assert_eq!(attributes.diagnostics.len(), 1);
+    assert_eq!(
+        attributes.diagnostics[0].to_string(),
+        "`buf`: `playground::RAND` expects 1 argument, found 0"
+    );
+    assert_eq!(attributes.entry_points.len(), 3);
+}
+
+#[test]
+fn resource_plan() {
+    let reflection = reflect("playground_attributes.slang");
+    let plan = playground::resource_plan(&reflection);
+    assert!(plan.diagnostics.is_empty());
+
+    let zeros = plan.buffer("zeros").unwrap();
+    assert_eq!(zeros.element_count, 1024);
+    assert_eq!(zeros.element_size, 4);
+    assert_eq!(zeros.size, 4096);
+    assert_eq!(zeros.contents, BufferContents::Zeros);
+    let noise = plan.buffer("noise").unwrap();
+    assert_eq!(noise.size, 131072 * 4);
+    assert_eq!(noise.contents, BufferContents::Random);
+    assert!(plan.buffer("scratch").is_none());
+
+    let canvas = plan.texture("canvas").unwrap();
+    assert_eq!(
+        canvas.source,
+        TextureSource::Black {
+            size: [256, 128, 1]
+        }
+    );
+    let volume = plan.texture("volume").unwrap();
+    assert!(matches!(volume.tex_type, TextureType::Dim3));
+    assert_eq!(volume.source.size(800, 600), Some([16, 16, 8]));
+    let output = plan.texture("outputTexture").unwrap();
+    assert_eq!(
+        output.source,
+        TextureSource::Screen {
+            width_scale: 1.0,
+            height_scale: 0.5
+        }
+    );
+    assert_eq!(output.source.size(800, 600), Some([800, 300, 1]));
+    let jeep = plan.texture("jeep").unwrap();
+    assert_eq!(
+        jeep.source,
+        TextureSource::Url("static/jeep.jpg".to_string())
+    );
+    assert_eq!(jeep.source.size(800, 600), None);
+    assert_eq!(plan.textures.len(), 4);
+
+    assert_eq!(
+        plan.aliases,
+        [TextureAlias {
+            name: "drawTexture".to_string(),
+            original: "outputTexture".to_string(),
+        }]
+    );
+    assert_eq!(plan.samplers, ["linearSampler"]);
+
+    let time = plan.uniform("time").unwrap();
+    assert_eq!((time.offset, time.size), (0, 4));
+    assert_eq!(time.source, UniformSource::Time);
+    let mouse = plan.uniform("mousePosition").unwrap();
+    assert_eq!((mouse.offset, mouse.size), (16, 16));
+    assert_eq!(
+        plan.uniforms
+            .iter()
+            .filter(|u| u.source.is_per_frame())
+            .map(|u| u.name.as_str())
+            .collect::<Vec<_>>(),
+        ["time", "deltaTime", "mousePosition", "spacePressed"]
+    );
+    assert_eq!(plan.uniforms.len(), 6);
+    assert_eq!(
+        plan.uniform_buffer_size,
+        reflection.global_uniform_buffer.unwrap().size
+    );
+}
+
+#[test]
+fn resource_plan_diagnostics() {
+    let mut reflection = reflect("playground_attributes.slang");
+    let attribute = |name: &str, parameter: UserAttributeParameter| UserAttributeReflection {
+        name: name.to_string(),
+        parameters: vec![parameter],
+    };
+    for variable in &mut reflection.variables {
+        match variable.name.as_str() {
+            "jeep" => {
+                variable.user_attributes = vec![attribute(
+                    "playground_RAND",
+                    UserAttributeParameter::Int(16),
+                )]
+            }
+            "drawTexture" => {
+                variable.user_attributes = vec![attribute(
+                    "playground_REBIND_FOR_DRAW",
+                    UserAttributeParameter::String("missing".to_string()),
+                )]
+            }
+            _ => {}
+        }
+    }
+
+    let plan = playground::resource_plan(&reflection);
+    let diagnostics = plan
+        .diagnostics
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        diagnostics,
+        [
+            "`jeep`: `playground::RAND` can only be used on a structured buffer",
+            "`drawTexture`: `playground::REBIND_FOR_DRAW` refers to `missing`, which is not a \
+             playground texture",
+        ]
+    );
+    assert!(plan.buffer("jeep").is_none());
+    assert!(plan.texture("jeep").is_none());
+    assert!(plan.aliases.is_empty());
+}
+
+#[test]
+fn dispatch_plan() {
+    let reflection = reflect("multi_entrypoint.slang");
+    let resources = playground::resource_plan(&reflection);
+    let plan = playground::dispatch_plan(&reflection, &resources);
+    assert!(plan.diagnostics.is_empty());
+    // RAND(131072) in workgroups of 64 threads.
+    for entry_point in ["fillBuffer", "fillBuffer2", "fillBuffer3"] {
+        assert_eq!(
+            plan.dispatch(entry_point),
+            Some(&Dispatch {
+                entry_point: entry_point.to_string(),
+                size: DispatchSize::Workgroups([2048, 1, 1]),
+                frequency: DispatchFrequency::Once,
+            })
+        );
+    }
+
+    let reflection = reflect("playground_attributes.slang");
+    let resources = playground::resource_plan(&reflection);
+    let plan = playground::dispatch_plan(&reflection, &resources);
+    assert!(plan.diagnostics.is_empty());
+    let render = plan.dispatch("render").unwrap();
+    assert_eq!(render.size, DispatchSize::Workgroups([32, 16, 1]));
+    assert_eq!(render.frequency, DispatchFrequency::EveryFrame);
+    let fill_noise = plan.dispatch("fillNoise").unwrap();
+    assert_eq!(fill_noise.size.workgroups(800, 600), Some([2048, 1, 1]));
+    assert_eq!(fill_noise.frequency, DispatchFrequency::Once);
+    assert_eq!(plan.dispatches.len(), 2);
+}
+
+#[test]
+fn dispatch_plan_diagnostics() {
+    let mut reflection = reflect("playground_attributes.slang");
+    let size_of = |name: &str| UserAttributeReflection {
+        name: "playground_CALL_SIZE_OF".to_string(),
+        parameters: vec![UserAttributeParameter::String(name.to_string())],
+    };
+    for entry_point in &mut reflection.entry_points {
+        match entry_point.name.as_str() {
+            "render" => entry_point.user_attributes = vec![size_of("outputTexture")],
+            "fillNoise" => entry_point.user_attributes = vec![size_of("nothing")],
+            "vertexMain" => entry_point.user_attributes = vec![size_of("scratch")],
+            _ => {}
+        }
+    }
+    let resources = playground::resource_plan(&reflection);
+    let plan = playground::dispatch_plan(&reflection, &resources);
+
+    // BLACK_SCREEN(1.0, 0.5) in workgroups of 8x8 threads.
+    let render = plan.dispatch("render").unwrap();
+    assert!(matches!(
+        &render.size,
+        DispatchSize::PerTexel { texture, .. } if texture == "outputTexture"
+    ));
+    assert_eq!(render.size.workgroups(800, 600), Some([100, 38, 1]));
+
+    let diagnostics = plan
+        .diagnostics
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>();
+    assert_eq!(
+        diagnostics,
+        [
+            "`fillNoise`: `playground::CALL::SIZE_OF` refers to `nothing`, which doesn't exist",
+            "`vertexMain`: `playground::CALL::SIZE_OF` needs a `[numthreads]` entry point",
+        ]
+    );
+    assert_eq!(plan.dispatches.len(), 1);
+
+    for entry_point in &mut reflection.entry_points {
+        if entry_point.name == "fillNoise" {
+            entry_point.user_attributes = vec![size_of("scratch")];
+        }
+    }
+    let plan = playground::dispatch_plan(&reflection, &resources);
+    assert_eq!(
+        plan.diagnostics[0].to_string(),
+        "`fillNoise`: `playground::CALL::SIZE_OF` refers to `scratch`, which has no playground \
+         size"
+    );
+}
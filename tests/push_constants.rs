@@ -0,0 +1,69 @@
+use slang_reflector::{
+    BoundParameter, CompileTarget, Reflector, ScalarType, SpecializationConstantValue,
+};
+
+#[test]
+fn push_constants() {
+    let reflection = Reflector::builder()
+        .search_path("tests")
+        .target(CompileTarget::Spirv)
+        .profile("spirv_1_5")
+        .module("push_constants.slang")
+        .reflect()
+        .unwrap();
+
+    let variable = |name: &str| {
+        &reflection
+            .variables
+            .iter()
+            .find(|v| v.name == name)
+            .unwrap_or_else(|| panic!("missing {name}"))
+            .reflection_type
+    };
+
+    let BoundParameter::PushConstants(block) = variable("params") else {
+        panic!("Expected push constant block");
+    };
+    assert_eq!(block.size, 32);
+    assert_eq!(block.range(), 0..32);
+    let fields = block
+        .variables
+        .iter()
+        .map(|v| v.name.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(fields, ["tint", "offset"]);
+
+    let BoundParameter::SpecializationConstant(exposure) = variable("exposure") else {
+        panic!("Expected specialization constant");
+    };
+    assert_eq!(exposure.constant_id, 7);
+    assert!(matches!(exposure.scalar_type, ScalarType::Float32));
+    assert_eq!(
+        exposure.default_value,
+        Some(SpecializationConstantValue::Float(1.5))
+    );
+
+    let BoundParameter::SpecializationConstant(use_fog) = variable("useFog") else {
+        panic!("Expected specialization constant");
+    };
+    assert_eq!(use_fog.constant_id, 8);
+    assert_eq!(
+        use_fog.default_value,
+        Some(SpecializationConstantValue::Bool(true))
+    );
+
+    let BoundParameter::SpecializationConstant(sample_count) = variable("sampleCount") else {
+        panic!("Expected specialization constant");
+    };
+    assert!(matches!(sample_count.scalar_type, ScalarType::Int32));
+    assert_eq!(
+        sample_count.default_value,
+        Some(SpecializationConstantValue::Int(4))
+    );
+    assert!(![7, 8].contains(&sample_count.constant_id));
+
+    assert!(matches!(
+        variable("outputBuffer"),
+        BoundParameter::Resource { .. }
+    ));
+}
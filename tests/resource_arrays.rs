@@ -0,0 +1,65 @@
+use slang_reflector::{BoundParameter, BoundResource, CompileTarget, DescriptorCount, Reflector};
+
+#[test]
+fn resource_arrays() {
+    let reflection = Reflector::builder()
+        .search_path("tests")
+        .target(CompileTarget::Wgsl)
+        .profile("spirv_1_6")
+        .module("resource_arrays.slang")
+        .reflect()
+        .unwrap();
+    let descriptor_count = |name: &str| {
+        let variable = reflection
+            .variables
+            .iter()
+            .find(|v| v.name == name)
+            .unwrap_or_else(|| panic!("Expected variable {name}"));
+        let BoundParameter::Resource {
+            resource: BoundResource::Array { element, count },
+            ..
+        } = &variable.reflection_type
+        else {
+            panic!("Expected resource array for {name}");
+        };
+        assert!(matches!(**element, BoundResource::Texture { .. }));
+        *count
+    };
+
+    assert_eq!(
+        descriptor_count("materialTextures"),
+        DescriptorCount::Fixed(16)
+    );
+    assert_eq!(
+        descriptor_count("bindlessTextures"),
+        DescriptorCount::Unbounded
+    );
+}
+
+#[test]
+fn dynamic_resource_table() {
+    let reflection = Reflector::builder()
+        .search_path("tests")
+        .target(CompileTarget::Spirv)
+        .profile("spirv_1_5")
+        .module("dynamic_resources.slang")
+        .reflect()
+        .unwrap();
+    let variable = reflection
+        .variables
+        .iter()
+        .find(|v| v.name == "resourceHeap")
+        .expect("Expected variable resourceHeap");
+    let BoundParameter::Resource {
+        resource: BoundResource::Array { element, count },
+        binding_index,
+        binding_space,
+        ..
+    } = &variable.reflection_type
+    else {
+        panic!("Expected resource array for resourceHeap");
+    };
+    assert!(matches!(**element, BoundResource::DynamicResource));
+    assert_eq!(*count, DescriptorCount::Unbounded);
+    assert_eq!((*binding_index, *binding_space), (0, 0));
+}
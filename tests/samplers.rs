@@ -0,0 +1,104 @@
+use slang_reflector::{
+    BoundParameter, BoundResource, CompileTarget, ProgramReflection, TextureType,
+};
+
+mod common;
+
+use common::{reflect, reflect_spirv, reflect_target};
+
+fn parameter<'a>(reflection: &'a ProgramReflection, name: &str) -> &'a BoundParameter {
+    &reflection
+        .variables
+        .iter()
+        .find(|v| v.name == name)
+        .unwrap_or_else(|| panic!("Expected variable {name}"))
+        .reflection_type
+}
+
+#[test]
+fn samplers() {
+    let reflection = reflect("samplers.slang");
+    let resource = |name: &str| {
+        let BoundParameter::Resource { resource, .. } = parameter(&reflection, name) else {
+            panic!("Expected resource parameter for {name}");
+        };
+        resource
+    };
+
+    assert!(matches!(
+        resource("linearSampler"),
+        BoundResource::Sampler {
+            is_comparison: false
+        }
+    ));
+    assert!(matches!(
+        resource("shadowSampler"),
+        BoundResource::Sampler {
+            is_comparison: true
+        }
+    ));
+    assert!(matches!(
+        resource("aliasedShadowSampler"),
+        BoundResource::Sampler {
+            is_comparison: true
+        }
+    ));
+    assert!(matches!(
+        resource("albedo"),
+        BoundResource::CombinedTextureSampler {
+            tex_type: TextureType::Dim2,
+            is_shadow: false,
+            ..
+        }
+    ));
+}
+
+/// `(texture binding, sampler binding)` of the combined `albedo`, each as `(index, space)`.
+fn albedo_bindings(reflection: &ProgramReflection) -> ((u32, u32), (u32, u32)) {
+    let BoundParameter::Resource {
+        resource:
+            BoundResource::CombinedTextureSampler {
+                sampler_binding_index,
+                sampler_binding_space,
+                ..
+            },
+        binding_index,
+        binding_space,
+        ..
+    } = parameter(reflection, "albedo")
+    else {
+        panic!("Expected combined texture-sampler for albedo");
+    };
+    (
+        (*binding_index, *binding_space),
+        (*sampler_binding_index, *sampler_binding_space),
+    )
+}
+
+#[test]
+fn combined_sampler_binding_wgsl() {
+    // WGSL has no combined samplers, so the sampler gets the slot after the texture's.
+    let (texture, sampler) = albedo_bindings(&reflect("samplers.slang"));
+    assert_eq!(texture, (3, 0));
+    assert_eq!(sampler, (4, 0));
+}
+
+#[test]
+fn combined_sampler_binding_spirv() {
+    // A combined image sampler is a single descriptor on Vulkan.
+    let (texture, sampler) = albedo_bindings(&reflect_spirv("samplers.slang"));
+    assert_eq!(texture, (3, 0));
+    assert_eq!(sampler, texture);
+}
+
+#[test]
+fn combined_sampler_binding_hlsl() {
+    // D3D binds the sampler in an `s` register, after `linearSampler` and `shadowSampler`.
+    let (texture, sampler) = albedo_bindings(&reflect_target(
+        "samplers.slang",
+        CompileTarget::Hlsl,
+        "sm_5_0",
+    ));
+    assert_eq!(texture, (1, 0));
+    assert_eq!(sampler, (2, 0));
+}
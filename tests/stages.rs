@@ -0,0 +1,47 @@
+use slang_reflector::{CompileTarget, Reflector, Stage};
+
+#[test]
+fn geometry_and_hull_shaders() {
+    let reflection = Reflector::builder()
+        .search_path("tests")
+        .target(CompileTarget::Spirv)
+        .profile("spirv_1_5")
+        .module("stages.slang")
+        .reflect()
+        .unwrap();
+
+    let geometry = reflection
+        .entry_points
+        .iter()
+        .find(|e| e.name == "geometryMain")
+        .expect("Expected geometryMain entry point");
+    assert_eq!(geometry.stage, Stage::Geometry);
+    assert!(geometry.inputs.iter().any(|i| i.name == "input"));
+    // The stream is reflected as the varyings of its element type.
+    let stream_outputs = geometry
+        .outputs
+        .iter()
+        .map(|o| o.name.as_str())
+        .filter(|name| name.starts_with("stream."))
+        .collect::<Vec<_>>();
+    assert_eq!(stream_outputs, ["stream.position", "stream.color"]);
+    assert!(geometry.skipped_varyings.is_empty());
+
+    let hull = reflection
+        .entry_points
+        .iter()
+        .find(|e| e.name == "hullMain")
+        .expect("Expected hullMain entry point");
+    assert_eq!(hull.stage, Stage::Hull);
+    let id = hull
+        .inputs
+        .iter()
+        .find(|i| i.name == "id")
+        .expect("Expected control point ID input");
+    assert_eq!(id.semantic_name.as_deref(), Some("SV_OutputControlPointID"));
+    // `InputPatch<T, N>` has no fields to reflect.
+    assert!(!hull.inputs.iter().any(|i| i.name.starts_with("patch")));
+    assert_eq!(hull.skipped_varyings, ["hullMain.patch"]);
+    assert!(hull.outputs.iter().any(|o| o.name == "result.position"));
+    assert!(hull.uniforms.is_empty());
+}
@@ -0,0 +1,92 @@
+use slang_reflector::{BoundParameter, BoundResource, CompileTarget, Reflector, TextureType};
+
+#[test]
+fn textures() {
+    let reflection = Reflector::builder()
+        .search_path("tests")
+        .target(CompileTarget::Wgsl)
+        .profile("spirv_1_6")
+        .module("textures.slang")
+        .reflect()
+        .unwrap();
+    let resource = |name: &str| {
+        let variable = reflection
+            .variables
+            .iter()
+            .find(|v| v.name == name)
+            .unwrap_or_else(|| panic!("Expected variable {name}"));
+        let BoundParameter::Resource { resource, .. } = &variable.reflection_type else {
+            panic!("Expected resource parameter for {name}");
+        };
+        resource
+    };
+    let texture = |name: &str| {
+        let BoundResource::Texture {
+            tex_type,
+            is_array,
+            is_multisampled,
+            is_shadow,
+            ..
+        } = resource(name)
+        else {
+            panic!("Expected texture resource for {name}");
+        };
+        (tex_type.clone(), *is_array, *is_multisampled, *is_shadow)
+    };
+
+    assert!(matches!(
+        texture("layers"),
+        (TextureType::Dim2, true, false, false)
+    ));
+    assert!(matches!(
+        texture("probes"),
+        (TextureType::Cube, true, false, false)
+    ));
+    assert!(matches!(
+        texture("msaaColor"),
+        (TextureType::Dim2, false, true, false)
+    ));
+    assert!(matches!(
+        texture("gradients"),
+        (TextureType::Dim1, true, false, false)
+    ));
+    assert!(matches!(
+        resource("texels"),
+        BoundResource::TexelBuffer { .. }
+    ));
+
+    assert!(matches!(
+        resource("feedback"),
+        BoundResource::FeedbackTexture {
+            tex_type: TextureType::Dim2,
+            is_array: false,
+        }
+    ));
+    assert!(matches!(
+        resource("feedbackLayers"),
+        BoundResource::FeedbackTexture {
+            tex_type: TextureType::Dim2,
+            is_array: true,
+        }
+    ));
+    assert!(matches!(
+        resource("shadowMap"),
+        BoundResource::CombinedTextureSampler {
+            tex_type: TextureType::Dim2,
+            is_array: false,
+            is_multisampled: false,
+            is_shadow: true,
+            ..
+        }
+    ));
+    assert!(matches!(
+        resource("shadowCascades"),
+        BoundResource::CombinedTextureSampler {
+            tex_type: TextureType::Dim2,
+            is_array: true,
+            is_multisampled: false,
+            is_shadow: true,
+            ..
+        }
+    ));
+}
@@ -0,0 +1,23 @@
+use slang_reflector::{
+    CompileTarget, ReflectErrorReason, Reflector, ReflectorError, ResourceShape,
+};
+
+#[test]
+fn unsupported() {
+    let error = Reflector::builder()
+        .search_path("tests")
+        .target(CompileTarget::Wgsl)
+        .profile("spirv_1_6")
+        .module("unsupported.slang")
+        .reflect()
+        .expect_err("Acceleration structures should not be reflected");
+    let ReflectorError::Reflect(error) = error else {
+        panic!("Expected reflection error, got {error}");
+    };
+    assert_eq!(error.path, "scene");
+    assert_eq!(error.reason, ReflectErrorReason::UnsupportedResourceShape);
+    assert_eq!(
+        error.resource_shape,
+        Some(ResourceShape::SlangAccelerationStructure)
+    );
+}
@@ -0,0 +1,118 @@
+use slang_reflector::{CompileTarget, Reflector};
+
+const SHADER: &str = r#"
+import playground;
+
+[playground::TIME]
+uniform float time;
+
+RWStructuredBuffer<float> outputBuffer;
+
+[shader("compute")]
+[numthreads(64, 1, 1)]
+void main(uint2 dispatchThreadId: SV_DispatchThreadID)
+{
+    outputBuffer[dispatchThreadId.x] = time;
+}
+"#;
+
+#[test]
+fn virtual_fs() {
+    let compiled_program = Reflector::builder()
+        .target(CompileTarget::Wgsl)
+        .profile("spirv_1_6")
+        .virtual_file("playground.slang", include_str!("playground.slang"))
+        .source("editor_buffer", SHADER)
+        .compile()
+        .unwrap();
+    let reflection = compiled_program.reflect().unwrap();
+
+    let time = reflection
+        .variables
+        .iter()
+        .find(|v| v.name == "time")
+        .expect("Expected time uniform");
+    assert_eq!(time.user_attributes[0].name, "playground_TIME");
+    assert!(
+        reflection
+            .variables
+            .iter()
+            .any(|v| v.name == "outputBuffer")
+    );
+    assert_eq!(reflection.entry_points[0].name, "main");
+}
+
+#[test]
+fn virtual_file_that_is_not_a_module() {
+    // Only compiles as part of a file that declares `Light`, so it isn't a module.
+    const FRAGMENT: &str = "float3 shade(Light light) { return light.color; }\n";
+
+    let compiled_program = Reflector::builder()
+        .target(CompileTarget::Wgsl)
+        .profile("spirv_1_6")
+        .virtual_file("lighting.slang", FRAGMENT)
+        .virtual_file("notes.txt", "not a shader")
+        .virtual_file("playground.slang", include_str!("playground.slang"))
+        .source("editor_buffer", SHADER)
+        .compile()
+        .unwrap();
+    let reflection = compiled_program.reflect().unwrap();
+    assert_eq!(reflection.entry_points[0].name, "main");
+}
+
+#[test]
+fn virtual_include_and_nested_import() {
+    const SHADER: &str = r#"
+import utils.math;
+
+struct Light
+{
+    float3 color;
+};
+
+#include "lighting.slang"
+
+RWStructuredBuffer<float3> outputBuffer;
+
+[shader("compute")]
+[numthreads(64, 1, 1)]
+void main(uint2 dispatchThreadId: SV_DispatchThreadID)
+{
+    Light light = { float3(1.0, 0.5, 0.25) };
+    outputBuffer[dispatchThreadId.x] = square(shade(light));
+}
+"#;
+
+    let compiled_program = Reflector::builder()
+        .target(CompileTarget::Wgsl)
+        .profile("spirv_1_6")
+        .virtual_file(
+            "lighting.slang",
+            "float3 shade(Light light) { return light.color; }\n",
+        )
+        .virtual_file(
+            "utils/math.slang",
+            "import utils.scale;\npublic float3 square(float3 x) { return x * x * scale(); }\n",
+        )
+        .virtual_file(
+            "utils/scale.slang",
+            "public float scale() { return 2.0; }\n",
+        )
+        .source("editor_buffer", SHADER)
+        .compile()
+        .unwrap();
+
+    let reflection = compiled_program.reflect().unwrap();
+    assert_eq!(reflection.entry_points[0].name, "main");
+    let dependency_files = compiled_program.dependency_files();
+    assert!(
+        dependency_files
+            .iter()
+            .any(|file| file.ends_with("lighting.slang"))
+    );
+    assert!(
+        dependency_files
+            .iter()
+            .any(|file| file.ends_with("utils/scale.slang"))
+    );
+}
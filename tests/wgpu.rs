@@ -0,0 +1,182 @@
+#![cfg(feature = "wgpu")]
+
+use slang_reflector::wgpu::{bind_group_layouts, bind_group_layouts_for, push_constant_ranges_for};
+use slang_reflector::{CompileTarget, Reflector};
+use wgpu::{
+    BindingType, BufferBindingType, SamplerBindingType, ShaderStages, StorageTextureAccess,
+    TextureFormat, TextureSampleType, TextureViewDimension,
+};
+
+#[test]
+fn cube_bind_groups() {
+    let reflection = Reflector::builder()
+        .search_path("tests")
+        .target(CompileTarget::Wgsl)
+        .profile("spirv_1_6")
+        .module("cube.slang")
+        .reflect()
+        .unwrap();
+
+    let groups = bind_group_layouts(&reflection).unwrap();
+    assert_eq!(groups.len(), 1);
+    let group = &groups[0];
+    assert_eq!(group.group, 0);
+
+    let globals = group.entry("$Globals").unwrap();
+    assert!(matches!(
+        globals.ty,
+        BindingType::Buffer {
+            ty: BufferBindingType::Uniform,
+            min_binding_size: Some(size),
+            ..
+        } if size.get() == 16
+    ));
+    assert_eq!(
+        globals.visibility,
+        ShaderStages::VERTEX | ShaderStages::FRAGMENT
+    );
+
+    assert_eq!(
+        group.entry("outputTexture").unwrap().ty,
+        BindingType::StorageTexture {
+            access: StorageTextureAccess::WriteOnly,
+            format: TextureFormat::Rgba8Unorm,
+            view_dimension: TextureViewDimension::D2,
+        }
+    );
+    assert_eq!(
+        group.entry("cubeTexture").unwrap().ty,
+        BindingType::Texture {
+            sample_type: TextureSampleType::Float { filterable: true },
+            view_dimension: TextureViewDimension::D2,
+            multisampled: false,
+        }
+    );
+    assert_eq!(
+        group.entry("sampler").unwrap().ty,
+        BindingType::Sampler(SamplerBindingType::Filtering)
+    );
+
+    let bindings = group.entries.iter().map(|e| e.binding).collect::<Vec<_>>();
+    assert!(bindings.is_sorted());
+
+    let fragment_groups = bind_group_layouts_for(&reflection, &["fragmentMain"]).unwrap();
+    assert!(
+        fragment_groups[0]
+            .entries
+            .iter()
+            .all(|e| e.visibility == ShaderStages::FRAGMENT)
+    );
+    assert!(bind_group_layouts_for(&reflection, &["missing"]).is_err());
+}
+
+#[test]
+fn storage_buffers() {
+    let reflection = Reflector::builder()
+        .search_path("tests")
+        .target(CompileTarget::Wgsl)
+        .profile("spirv_1_6")
+        .module("layout.slang")
+        .reflect()
+        .unwrap();
+
+    let groups = bind_group_layouts(&reflection).unwrap();
+    let particles = groups[0].entry("particles").unwrap();
+    assert_eq!(particles.visibility, ShaderStages::COMPUTE);
+    assert!(matches!(
+        particles.ty,
+        BindingType::Buffer {
+            ty: BufferBindingType::Storage { read_only: false },
+            min_binding_size: Some(size),
+            ..
+        } if size.get() == 48
+    ));
+}
+
+#[test]
+fn combined_sampler_is_unsupported() {
+    let reflection = Reflector::builder()
+        .search_path("tests")
+        .target(CompileTarget::Wgsl)
+        .profile("spirv_1_6")
+        .module("samplers.slang")
+        .reflect()
+        .unwrap();
+
+    let error = bind_group_layouts(&reflection).unwrap_err();
+    assert_eq!(error.path, "albedo");
+}
+
+#[test]
+fn texture_1d_array_is_unsupported() {
+    let reflection = Reflector::builder()
+        .target(CompileTarget::Wgsl)
+        .profile("spirv_1_6")
+        .source(
+            "texture_1d_array",
+            r#"
+            Texture1DArray<float4> gradients;
+            RWStructuredBuffer<float4> outputBuffer;
+
+            [shader("compute")]
+            [numthreads(64, 1, 1)]
+            void main(uint2 id: SV_DispatchThreadID)
+            {
+                outputBuffer[id.x] = gradients.Load(int3(id.x, id.y, 0));
+            }
+            "#,
+        )
+        .reflect()
+        .unwrap();
+
+    let error = bind_group_layouts(&reflection).unwrap_err();
+    assert_eq!(error.path, "gradients");
+}
+
+#[test]
+fn entry_point_uniform_buffer() {
+    let reflection = Reflector::builder()
+        .search_path("tests")
+        .target(CompileTarget::Wgsl)
+        .profile("spirv_1_6")
+        .module("entry_point_uniforms.slang")
+        .reflect()
+        .unwrap();
+
+    let groups = bind_group_layouts(&reflection).unwrap();
+    let params = groups[0].entry("main.$Params").unwrap();
+    assert!(matches!(
+        params.ty,
+        BindingType::Buffer {
+            ty: BufferBindingType::Uniform,
+            min_binding_size: Some(size),
+            ..
+        } if size.get() >= 20
+    ));
+    assert_eq!(params.visibility, ShaderStages::COMPUTE);
+    assert!(groups[0].entry("outputBuffer").is_some());
+}
+
+#[test]
+fn push_constant_ranges_per_stage() {
+    let reflection = Reflector::builder()
+        .search_path("tests")
+        .target(CompileTarget::Spirv)
+        .profile("spirv_1_5")
+        .module("push_constant_stages.slang")
+        .reflect()
+        .unwrap();
+
+    let mut ranges =
+        push_constant_ranges_for(&reflection, &["vertexMain", "fragmentMain"]).unwrap();
+    ranges.sort_by_key(|r| r.range.end);
+    assert_eq!(ranges.len(), 2);
+    assert_eq!(ranges[0].stages, ShaderStages::VERTEX);
+    assert_eq!(ranges[0].range, 0..32);
+    assert_eq!(ranges[1].stages, ShaderStages::FRAGMENT);
+    assert_eq!(ranges[1].range, 0..64);
+
+    let vertex_only = push_constant_ranges_for(&reflection, &["vertexMain"]).unwrap();
+    assert_eq!(vertex_only.len(), 1);
+    assert_eq!(vertex_only[0].range, 0..32);
+}